@@ -48,3 +48,17 @@ fn bench_format_a_big_block_of_data(b: &mut Bencher) {
 
     b.iter(|| format!("{}", row_view));
 }
+
+#[bench]
+fn bench_format_a_big_block_of_data_with_many_colors(b: &mut Bencher) {
+    let data: Vec<u8> = (0u32..1024 * 1024).map(|v| (v % 256) as u8).collect();
+    let colors: color::Colors = (0..10_000usize)
+        .map(|n| (if n % 2 == 0 { color::red() } else { color::blue_bold() }, n * 100..n * 100 + 60))
+        .collect();
+
+    let row_view = HexViewBuilder::new(&data)
+        .add_colors(colors)
+        .finish();
+
+    b.iter(|| format!("{}", row_view));
+}
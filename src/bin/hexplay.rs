@@ -0,0 +1,317 @@
+extern crate hexplay;
+extern crate termcolor;
+
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::process;
+
+use hexplay::{HexStream, HexViewBuilder, CODEPAGE_0037, CODEPAGE_0273, CODEPAGE_0500, CODEPAGE_0850, CODEPAGE_1047, CODEPAGE_1252, CODEPAGE_ASCII};
+use termcolor::{ColorChoice, StandardStream};
+
+const USAGE: &str = "Usage: hexplay [OPTIONS] [FILE]
+
+Prints FILE, or the standard input when FILE is missing or '-', like a hex editor.
+
+Options:
+  -s, --seek OFFSET            start at OFFSET bytes into the input
+  -l, --length LENGTH          stop after LENGTH bytes
+  -c, --cols WIDTH             print WIDTH bytes per row (default: 16)
+      --codepage CODEPAGE      ascii, 850, 1252 or one of the EBCDIC code pages
+                               037, 273, 500 and 1047 (default: 850)
+      --replacement-char CHAR  print CHAR for unprintable bytes (default: .)
+      --color WHEN             auto, always or never (default: auto)
+  -h, --help                   print this help
+  -V, --version                print the version
+
+Numbers can be given in decimal or, prefixed with 0x, in hexadecimal.";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ColorWhen {
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    seek: u64,
+    length: Option<u64>,
+    row_width: usize,
+    codepage: &'static [char],
+    replacement_character: char,
+    color: ColorWhen,
+    file: Option<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            seek: 0,
+            length: None,
+            row_width: 16,
+            codepage: CODEPAGE_0850,
+            replacement_character: '.',
+            color: ColorWhen::Auto,
+            file: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Dump(Options),
+    Help,
+    Version,
+}
+
+#[derive(Debug)]
+enum Error {
+    Usage(String),
+    Io(String, io::Error),
+    SeekPastEnd { seek: u64, size: u64 },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Usage(ref message) => write!(f, "{}", message),
+            Error::Io(ref context, ref e) => write!(f, "{}: {}", context, e),
+            Error::SeekPastEnd { seek, size } => write!(f, "can not seek to offset {}, the input is only {} bytes long", seek, size),
+        }
+    }
+}
+
+fn parse_number(option: &str, value: &str) -> Result<u64, Error> {
+    let parsed = if value.starts_with("0x") || value.starts_with("0X") {
+        u64::from_str_radix(&value[2..], 16)
+    } else {
+        value.parse()
+    };
+    parsed.map_err(|_| Error::Usage(format!("invalid number '{}' for {}", value, option)))
+}
+
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Command, Error> {
+    let mut options = Options::default();
+
+    while let Some(arg) = args.next() {
+        if arg == "-" || !arg.starts_with('-') {
+            if options.file.is_some() {
+                return Err(Error::Usage(format!("unexpected argument '{}'", arg)));
+            }
+            options.file = Some(arg);
+            continue;
+        }
+
+        let (option, inline_value) = match arg.find('=') {
+            Some(i) if arg.starts_with("--") => (arg[..i].to_string(), Some(arg[i + 1..].to_string())),
+            _ => (arg.clone(), None),
+        };
+        let mut value = || inline_value.clone().or_else(|| args.next())
+            .ok_or_else(|| Error::Usage(format!("missing value for {}", option)));
+
+        match option.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-V" | "--version" => return Ok(Command::Version),
+            "-s" | "--seek" => options.seek = parse_number(&option, &value()?)?,
+            "-l" | "--length" => options.length = Some(parse_number(&option, &value()?)?),
+            "-c" | "--cols" => {
+                let value = value()?;
+                options.row_width = parse_number(&option, &value)? as usize;
+                if options.row_width == 0 {
+                    return Err(Error::Usage(format!("invalid row width '{}'", value)));
+                }
+            },
+            "--codepage" => {
+                options.codepage = match value()?.as_str() {
+                    "ascii" => CODEPAGE_ASCII,
+                    "850" => CODEPAGE_0850,
+                    "1252" => CODEPAGE_1252,
+                    "037" => CODEPAGE_0037,
+                    "273" => CODEPAGE_0273,
+                    "500" => CODEPAGE_0500,
+                    "1047" => CODEPAGE_1047,
+                    other => return Err(Error::Usage(format!("unknown codepage '{}'", other))),
+                }
+            },
+            "--replacement-char" => {
+                let value = value()?;
+                let mut chars = value.chars();
+                options.replacement_character = match (chars.next(), chars.next()) {
+                    (Some(ch), None) => ch,
+                    _ => return Err(Error::Usage(format!("invalid replacement character '{}'", value))),
+                }
+            },
+            "--color" => {
+                options.color = match value()?.as_str() {
+                    "auto" => ColorWhen::Auto,
+                    "always" => ColorWhen::Always,
+                    "never" => ColorWhen::Never,
+                    other => return Err(Error::Usage(format!("invalid color choice '{}'", other))),
+                }
+            },
+            _ => return Err(Error::Usage(format!("unknown option '{}'", arg))),
+        }
+    }
+
+    Ok(Command::Dump(options))
+}
+
+/// Opens the input and positions it at the seek offset.
+fn open(options: &Options) -> Result<Box<dyn Read>, Error> {
+    match options.file {
+        Some(ref path) if path != "-" => {
+            let mut file = File::open(path).map_err(|e| Error::Io(path.clone(), e))?;
+            let metadata = file.metadata().map_err(|e| Error::Io(path.clone(), e))?;
+            // Only regular files have a meaningful size, devices and pipes are seeked or skipped
+            if metadata.is_file() {
+                if options.seek > metadata.len() {
+                    return Err(Error::SeekPastEnd { seek: options.seek, size: metadata.len() });
+                }
+                file.seek(SeekFrom::Start(options.seek)).map_err(|e| Error::Io(path.clone(), e))?;
+            } else if file.seek(SeekFrom::Start(options.seek)).is_err() {
+                skip(&mut file, options.seek, path)?;
+            }
+            Ok(Box::new(file))
+        },
+        _ => {
+            let mut stdin = io::stdin();
+            skip(&mut stdin, options.seek, "<stdin>")?;
+            Ok(Box::new(stdin))
+        },
+    }
+}
+
+/// Reads and drops the first `seek` bytes of `input`, which is called `name` in errors.
+fn skip<R: Read>(input: &mut R, seek: u64, name: &str) -> Result<(), Error> {
+    let skipped = io::copy(&mut input.take(seek), &mut io::sink())
+        .map_err(|e| Error::Io(name.to_string(), e))?;
+    if skipped < seek {
+        return Err(Error::SeekPastEnd { seek, size: skipped });
+    }
+    Ok(())
+}
+
+fn dump(options: &Options) -> Result<(), Error> {
+    let mut input = open(options)?;
+    if let Some(length) = options.length {
+        input = Box::new(input.take(length));
+    }
+
+    let mut builder = HexViewBuilder::new(&[])
+        .address_offset(options.seek as usize)
+        .row_width(options.row_width)
+        .codepage(options.codepage)
+        .replacement_character(options.replacement_character);
+    if options.color == ColorWhen::Always {
+        builder = builder.force_color();
+    }
+
+    let mut stream = HexStream::new(input, builder.finish());
+    let result = match options.color {
+        ColorWhen::Never => stream.fmt(&mut StandardStream::stdout(ColorChoice::Never).lock()),
+        _ => stream.print(),
+    };
+    result.and_then(|()| writeln!(io::stdout()))
+        .map_err(|e| Error::Io("dump failed".to_string(), e))
+}
+
+fn main() {
+    let result = parse_args(env::args().skip(1)).and_then(|command| match command {
+        Command::Help => {
+            println!("{}", USAGE);
+            Ok(())
+        },
+        Command::Version => {
+            println!("hexplay {}", env!("CARGO_PKG_VERSION"));
+            Ok(())
+        },
+        Command::Dump(options) => dump(&options),
+    });
+
+    match result {
+        Ok(()) => {},
+        Err(Error::Io(_, ref e)) if e.kind() == io::ErrorKind::BrokenPipe => {},
+        Err(e @ Error::Usage(_)) => {
+            eprintln!("hexplay: {}\n\n{}", e, USAGE);
+            process::exit(2);
+        },
+        Err(e) => {
+            eprintln!("hexplay: {}", e);
+            process::exit(1);
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Command, Error> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn the_defaults_dump_stdin() {
+        assert_eq!(parse(&[]).unwrap(), Command::Dump(Options::default()));
+    }
+
+    #[test]
+    fn all_options_can_be_given() {
+        let command = parse(&["-s", "0x10", "--length=32", "-c", "8", "--codepage", "1252", "--replacement-char", "?", "--color", "never", "image.bin"]).unwrap();
+
+        assert_eq!(command, Command::Dump(Options {
+            seek: 16,
+            length: Some(32),
+            row_width: 8,
+            codepage: CODEPAGE_1252,
+            replacement_character: '?',
+            color: ColorWhen::Never,
+            file: Some("image.bin".to_string()),
+        }));
+    }
+
+    #[test]
+    fn invalid_arguments_are_usage_errors() {
+        for args in &[&["-c", "0"][..], &["-s", "ten"], &["--codepage", "437"], &["--replacement-char", "ab"], &["--color", "sometimes"], &["-l"], &["--bogus"], &["a", "b"]] {
+            match parse(args) {
+                Err(Error::Usage(_)) => {},
+                other => panic!("{:?} was parsed as {:?}", args, other),
+            }
+        }
+    }
+
+    #[test]
+    fn help_and_version_take_precedence() {
+        assert_eq!(parse(&["-c", "8", "--help"]).unwrap(), Command::Help);
+        assert_eq!(parse(&["-V"]).unwrap(), Command::Version);
+    }
+
+    #[test]
+    fn skipping_reads_past_the_skipped_bytes() {
+        let mut input = io::Cursor::new(vec![1, 2, 3, 4]);
+
+        skip(&mut input, 3, "input").unwrap();
+        let mut rest = Vec::new();
+        input.read_to_end(&mut rest).unwrap();
+
+        assert_eq!(rest, vec![4]);
+        match skip(&mut io::Cursor::new(vec![1, 2]), 3, "input") {
+            Err(Error::SeekPastEnd { seek: 3, size: 2 }) => {},
+            other => panic!("skipping past the end gave {:?}", other),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn devices_without_a_size_can_be_seeked() {
+        let options = Options { seek: 0x1000, file: Some("/dev/zero".to_string()), ..Options::default() };
+
+        let mut input = open(&options).unwrap();
+        let mut buf = [1; 4];
+        input.read_exact(&mut buf).unwrap();
+
+        assert_eq!(buf, [0; 4]);
+    }
+}
@@ -1,5 +1,7 @@
 use std;
 
+use format::Endian;
+
 pub const NIL: char = std::char::REPLACEMENT_CHARACTER;
 
 /// The mapping for [ASCII](https://en.wikipedia.org/wiki/ASCII)
@@ -7,7 +9,7 @@ pub const NIL: char = std::char::REPLACEMENT_CHARACTER;
 /// This mapping uses the standard ascii character set which is composed of a
 /// 7-bit code (or 128 characters). The first 32 characters and the last one
 /// are non-printable control characters.
-pub const CODEPAGE_ASCII: &'static [char]  = &[
+pub const CODEPAGE_ASCII: &[char]  = &[
 //   0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
     NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 0
     NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 1
@@ -31,7 +33,7 @@ pub const CODEPAGE_ASCII: &'static [char]  = &[
 ///
 /// This code page is also known as `DOS/IBM-ASCII` and is used as the default
 /// code page by this library.
-pub const CODEPAGE_0850: &'static [char]  = &[
+pub const CODEPAGE_0850: &[char]  = &[
 //   0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
     NIL,  '☺',  '☻',  '♥',  '♦',  '♣',  '♠',  '•',  '◘',  '○',  '◙',  '♂',  '♀',  '♪',  '♫',  '☼', // 0
     '►',  '◄',  '↕',  '‼',  '¶',  '§',  '▬',  '↨',  '↑',  '↓',  '→',  '←',  '∟',  '↔',  '▲',  '▼', // 1
@@ -54,7 +56,7 @@ pub const CODEPAGE_0850: &'static [char]  = &[
 /// The mapping for [code page 1252](https://en.wikipedia.org/wiki/Code_page_1252)
 ///
 /// This code page is also known as `Latin 1 Windows` or `ANSI`.
-pub const CODEPAGE_1252: &'static [char] = &[
+pub const CODEPAGE_1252: &[char] = &[
 //   0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
     NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 0
     NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 1
@@ -74,6 +76,100 @@ pub const CODEPAGE_1252: &'static [char] = &[
     'ð',  'ñ',  'ò',  'ó',  'ô',  'õ',  'ö',  '÷',  'ø',  'ù',  'ú',  'û',  'ü',  'ý',  'þ',  'ÿ', // F
 ];
 
+/// The mapping for [code page 37](https://en.wikipedia.org/wiki/EBCDIC_037)
+///
+/// This EBCDIC code page is also known as `CCSID 37` and is used on IBM mainframes in the
+/// USA, Canada and other English speaking countries.
+pub const CODEPAGE_0037: &[char] = &[
+//   0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 0
+    NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 1
+    NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 2
+    NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 3
+    ' ',  ' ',  'â',  'ä',  'à',  'á',  'ã',  'å',  'ç',  'ñ',  '¢',  '.',  '<',  '(',  '+',  '|', // 4
+    '&',  'é',  'ê',  'ë',  'è',  'í',  'î',  'ï',  'ì',  'ß',  '!',  '$',  '*',  ')',  ';',  '¬', // 5
+    '-',  '/',  'Â',  'Ä',  'À',  'Á',  'Ã',  'Å',  'Ç',  'Ñ',  '¦',  ',',  '%',  '_',  '>',  '?', // 6
+    'ø',  'É',  'Ê',  'Ë',  'È',  'Í',  'Î',  'Ï',  'Ì',  '`',  ':',  '#',  '@', '\'',  '=',  '"', // 7
+    'Ø',  'a',  'b',  'c',  'd',  'e',  'f',  'g',  'h',  'i',  '«',  '»',  'ð',  'ý',  'þ',  '±', // 8
+    '°',  'j',  'k',  'l',  'm',  'n',  'o',  'p',  'q',  'r',  'ª',  'º',  'æ',  '¸',  'Æ',  '¤', // 9
+    'µ',  '~',  's',  't',  'u',  'v',  'w',  'x',  'y',  'z',  '¡',  '¿',  'Ð',  'Ý',  'Þ',  '®', // A
+    '^',  '£',  '¥',  '·',  '©',  '§',  '¶',  '¼',  '½',  '¾',  '[',  ']',  '¯',  '¨',  '´',  '×', // B
+    '{',  'A',  'B',  'C',  'D',  'E',  'F',  'G',  'H',  'I',  NIL,  'ô',  'ö',  'ò',  'ó',  'õ', // C
+    '}',  'J',  'K',  'L',  'M',  'N',  'O',  'P',  'Q',  'R',  '¹',  'û',  'ü',  'ù',  'ú',  'ÿ', // D
+   '\\',  '÷',  'S',  'T',  'U',  'V',  'W',  'X',  'Y',  'Z',  '²',  'Ô',  'Ö',  'Ò',  'Ó',  'Õ', // E
+    '0',  '1',  '2',  '3',  '4',  '5',  '6',  '7',  '8',  '9',  '³',  'Û',  'Ü',  'Ù',  'Ú',  NIL, // F
+];
+
+/// The mapping for [code page 500](https://en.wikipedia.org/wiki/EBCDIC_500)
+///
+/// This EBCDIC code page is also known as `International EBCDIC` or `Latin 1 EBCDIC`.
+pub const CODEPAGE_0500: &[char] = &[
+//   0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 0
+    NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 1
+    NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 2
+    NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 3
+    ' ',  ' ',  'â',  'ä',  'à',  'á',  'ã',  'å',  'ç',  'ñ',  '[',  '.',  '<',  '(',  '+',  '!', // 4
+    '&',  'é',  'ê',  'ë',  'è',  'í',  'î',  'ï',  'ì',  'ß',  ']',  '$',  '*',  ')',  ';',  '^', // 5
+    '-',  '/',  'Â',  'Ä',  'À',  'Á',  'Ã',  'Å',  'Ç',  'Ñ',  '¦',  ',',  '%',  '_',  '>',  '?', // 6
+    'ø',  'É',  'Ê',  'Ë',  'È',  'Í',  'Î',  'Ï',  'Ì',  '`',  ':',  '#',  '@', '\'',  '=',  '"', // 7
+    'Ø',  'a',  'b',  'c',  'd',  'e',  'f',  'g',  'h',  'i',  '«',  '»',  'ð',  'ý',  'þ',  '±', // 8
+    '°',  'j',  'k',  'l',  'm',  'n',  'o',  'p',  'q',  'r',  'ª',  'º',  'æ',  '¸',  'Æ',  '¤', // 9
+    'µ',  '~',  's',  't',  'u',  'v',  'w',  'x',  'y',  'z',  '¡',  '¿',  'Ð',  'Ý',  'Þ',  '®', // A
+    '¢',  '£',  '¥',  '·',  '©',  '§',  '¶',  '¼',  '½',  '¾',  '¬',  '|',  '¯',  '¨',  '´',  '×', // B
+    '{',  'A',  'B',  'C',  'D',  'E',  'F',  'G',  'H',  'I',  NIL,  'ô',  'ö',  'ò',  'ó',  'õ', // C
+    '}',  'J',  'K',  'L',  'M',  'N',  'O',  'P',  'Q',  'R',  '¹',  'û',  'ü',  'ù',  'ú',  'ÿ', // D
+   '\\',  '÷',  'S',  'T',  'U',  'V',  'W',  'X',  'Y',  'Z',  '²',  'Ô',  'Ö',  'Ò',  'Ó',  'Õ', // E
+    '0',  '1',  '2',  '3',  '4',  '5',  '6',  '7',  '8',  '9',  '³',  'Û',  'Ü',  'Ù',  'Ú',  NIL, // F
+];
+
+/// The mapping for [code page 1047](https://en.wikipedia.org/wiki/EBCDIC_1047)
+///
+/// This EBCDIC code page is also known as `Open Systems Latin 1` and is used by the Unix
+/// System Services of z/OS.
+pub const CODEPAGE_1047: &[char] = &[
+//   0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 0
+    NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 1
+    NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 2
+    NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 3
+    ' ',  ' ',  'â',  'ä',  'à',  'á',  'ã',  'å',  'ç',  'ñ',  '¢',  '.',  '<',  '(',  '+',  '|', // 4
+    '&',  'é',  'ê',  'ë',  'è',  'í',  'î',  'ï',  'ì',  'ß',  '!',  '$',  '*',  ')',  ';',  '^', // 5
+    '-',  '/',  'Â',  'Ä',  'À',  'Á',  'Ã',  'Å',  'Ç',  'Ñ',  '¦',  ',',  '%',  '_',  '>',  '?', // 6
+    'ø',  'É',  'Ê',  'Ë',  'È',  'Í',  'Î',  'Ï',  'Ì',  '`',  ':',  '#',  '@', '\'',  '=',  '"', // 7
+    'Ø',  'a',  'b',  'c',  'd',  'e',  'f',  'g',  'h',  'i',  '«',  '»',  'ð',  'ý',  'þ',  '±', // 8
+    '°',  'j',  'k',  'l',  'm',  'n',  'o',  'p',  'q',  'r',  'ª',  'º',  'æ',  '¸',  'Æ',  '¤', // 9
+    'µ',  '~',  's',  't',  'u',  'v',  'w',  'x',  'y',  'z',  '¡',  '¿',  'Ð',  '[',  'Þ',  '®', // A
+    '¬',  '£',  '¥',  '·',  '©',  '§',  '¶',  '¼',  '½',  '¾',  'Ý',  '¨',  '¯',  ']',  '´',  '×', // B
+    '{',  'A',  'B',  'C',  'D',  'E',  'F',  'G',  'H',  'I',  NIL,  'ô',  'ö',  'ò',  'ó',  'õ', // C
+    '}',  'J',  'K',  'L',  'M',  'N',  'O',  'P',  'Q',  'R',  '¹',  'û',  'ü',  'ù',  'ú',  'ÿ', // D
+   '\\',  '÷',  'S',  'T',  'U',  'V',  'W',  'X',  'Y',  'Z',  '²',  'Ô',  'Ö',  'Ò',  'Ó',  'Õ', // E
+    '0',  '1',  '2',  '3',  '4',  '5',  '6',  '7',  '8',  '9',  '³',  'Û',  'Ü',  'Ù',  'Ú',  NIL, // F
+];
+
+/// The mapping for [code page 273](https://en.wikipedia.org/wiki/EBCDIC_273)
+///
+/// This EBCDIC code page is used on IBM mainframes in Germany and Austria.
+pub const CODEPAGE_0273: &[char] = &[
+//   0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 0
+    NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 1
+    NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 2
+    NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 3
+    ' ',  ' ',  'â',  '{',  'à',  'á',  'ã',  'å',  'ç',  'ñ',  'Ä',  '.',  '<',  '(',  '+',  '!', // 4
+    '&',  'é',  'ê',  'ë',  'è',  'í',  'î',  'ï',  'ì',  '~',  'Ü',  '$',  '*',  ')',  ';',  '^', // 5
+    '-',  '/',  'Â',  '[',  'À',  'Á',  'Ã',  'Å',  'Ç',  'Ñ',  'ö',  ',',  '%',  '_',  '>',  '?', // 6
+    'ø',  'É',  'Ê',  'Ë',  'È',  'Í',  'Î',  'Ï',  'Ì',  '`',  ':',  '#',  '§', '\'',  '=',  '"', // 7
+    'Ø',  'a',  'b',  'c',  'd',  'e',  'f',  'g',  'h',  'i',  '«',  '»',  'ð',  'ý',  'þ',  '±', // 8
+    '°',  'j',  'k',  'l',  'm',  'n',  'o',  'p',  'q',  'r',  'ª',  'º',  'æ',  '¸',  'Æ',  '¤', // 9
+    'µ',  'ß',  's',  't',  'u',  'v',  'w',  'x',  'y',  'z',  '¡',  '¿',  'Ð',  'Ý',  'Þ',  '®', // A
+    '¢',  '£',  '¥',  '·',  '©',  '@',  '¶',  '¼',  '½',  '¾',  '¬',  '|',  '‾',  '¨',  '´',  '×', // B
+    'ä',  'A',  'B',  'C',  'D',  'E',  'F',  'G',  'H',  'I',  NIL,  'ô',  '¦',  'ò',  'ó',  'õ', // C
+    'ü',  'J',  'K',  'L',  'M',  'N',  'O',  'P',  'Q',  'R',  '¹',  'û',  '}',  'ù',  'ú',  'ÿ', // D
+    'Ö',  '÷',  'S',  'T',  'U',  'V',  'W',  'X',  'Y',  'Z',  '²',  'Ô', '\\',  'Ò',  'Ó',  'Õ', // E
+    '0',  '1',  '2',  '3',  '4',  '5',  '6',  '7',  '8',  '9',  '³',  'Û',  ']',  'Ù',  'Ú',  NIL, // F
+];
+
 fn contains(byte: u8, codepage: &[char]) -> bool {
     (byte as usize) < codepage.len()
 }
@@ -92,7 +188,104 @@ pub fn as_char(byte: u8, codepage: &[char], repl_char: char) -> char {
         return repl_char;
     }
 
-    return codepage[byte as usize];
+    codepage[byte as usize]
+}
+
+/// The ranges of the code points that do not take exactly one column in a terminal: the East Asian
+/// Wide and Fullwidth characters and the emoji, which take two, and the combining marks and
+/// zero-width characters, which take none.
+const NOT_SINGLE_COLUMN: &[(u32, u32)] = &[
+    (0x0300, 0x036F), (0x0483, 0x0489), (0x0591, 0x05BD), (0x0610, 0x061A), (0x064B, 0x065F),
+    (0x1100, 0x115F), (0x1AB0, 0x1AFF), (0x1DC0, 0x1DFF), (0x200B, 0x200F), (0x2028, 0x202E),
+    (0x2060, 0x2064), (0x20D0, 0x20FF), (0x231A, 0x231B), (0x2329, 0x232A), (0x23E9, 0x23EC),
+    (0x23F0, 0x23F0), (0x23F3, 0x23F3), (0x25FD, 0x25FE), (0x2614, 0x2615), (0x2648, 0x2653),
+    (0x267F, 0x267F), (0x2693, 0x2693), (0x26A1, 0x26A1), (0x26AA, 0x26AB), (0x26BD, 0x26BE),
+    (0x26C4, 0x26C5), (0x26CE, 0x26CE), (0x26D4, 0x26D4), (0x26EA, 0x26EA), (0x26F2, 0x26F3),
+    (0x26F5, 0x26F5), (0x26FA, 0x26FA), (0x26FD, 0x26FD), (0x2705, 0x2705), (0x270A, 0x270B),
+    (0x2728, 0x2728), (0x274C, 0x274C), (0x274E, 0x274E), (0x2753, 0x2755), (0x2757, 0x2757),
+    (0x2795, 0x2797), (0x27B0, 0x27B0), (0x27BF, 0x27BF), (0x2B1B, 0x2B1C), (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55), (0x2E80, 0x303E), (0x3041, 0x33FF), (0x3400, 0x4DBF), (0x4E00, 0x9FFF),
+    (0xA000, 0xA4CF), (0xA960, 0xA97F), (0xAC00, 0xD7A3), (0xF900, 0xFAFF), (0xFE00, 0xFE0F),
+    (0xFE10, 0xFE19), (0xFE20, 0xFE6F), (0xFEFF, 0xFEFF), (0xFF00, 0xFF60), (0xFFE0, 0xFFE6),
+    (0x16FE0, 0x16FE4), (0x17000, 0x18AFF), (0x1B000, 0x1B2FF), (0x1F004, 0x1F004), (0x1F0CF, 0x1F0CF),
+    (0x1F18E, 0x1F18E), (0x1F191, 0x1F19A), (0x1F200, 0x1F251), (0x1F300, 0x1F64F), (0x1F680, 0x1F6FF),
+    (0x1F7E0, 0x1F7EB), (0x1F900, 0x1F9FF), (0x1FA70, 0x1FAFF), (0x20000, 0x2FFFD), (0x30000, 0x3FFFD),
+    (0xE0100, 0xE01EF),
+];
+
+/// Returns `ch`, or `repl_char` if `ch` does not take exactly one column and would shift the
+/// columns following it.
+fn single_column(ch: char, repl_char: char) -> char {
+    let code_point = ch as u32;
+    let is_listed = NOT_SINGLE_COLUMN
+        .binary_search_by(|&(first, last)| if last < code_point {
+            std::cmp::Ordering::Less
+        } else if first > code_point {
+            std::cmp::Ordering::Greater
+        } else {
+            std::cmp::Ordering::Equal
+        })
+        .is_ok();
+    if is_listed { repl_char } else { ch }
+}
+
+/// Returns the character shown for the byte at `index` of the UTF-8 encoded `data`: the decoded
+/// character for the lead byte of a sequence, `filler` for its continuation bytes and `repl_char`
+/// for bytes that are not part of a valid sequence or that encode a control character, or a
+/// character that does not take exactly one column, like the wide CJK characters and emoji.
+pub fn utf8_char_at(data: &[u8], index: usize, repl_char: char, filler: char) -> char {
+    let is_continuation = |byte: u8| byte & 0xC0 == 0x80;
+    let lead = match (0..std::cmp::min(index, 3) + 1).map(|n| index - n).find(|&i| !is_continuation(data[i])) {
+        Some(lead) => lead,
+        None => return repl_char,
+    };
+
+    match decode_utf8(&data[lead..]) {
+        Some((ch, _)) if lead == index && !ch.is_control() => single_column(ch, repl_char),
+        Some((_, len)) if lead < index && index < lead + len => filler,
+        _ => repl_char,
+    }
+}
+
+/// Returns the character shown for the code unit at `index` of the UTF-16 encoded `data`: the decoded
+/// character for a code unit that starts a character, `filler` for the second half of a surrogate
+/// pair and `repl_char` for unpaired surrogates, incomplete code units, control characters and
+/// characters that do not take exactly one column.
+pub fn utf16_char_at(data: &[u8], index: usize, byte_order: Endian, repl_char: char, filler: char) -> char {
+    let unit = |i: usize| data.get(i..i + 2).map(|bytes| match byte_order {
+        Endian::Big => u16::from_be_bytes([bytes[0], bytes[1]]),
+        Endian::Little => u16::from_le_bytes([bytes[0], bytes[1]]),
+    });
+    let is_high_surrogate = |unit: u16| (0xD800..0xDC00).contains(&unit);
+    let is_low_surrogate = |unit: u16| (0xDC00..0xE000).contains(&unit);
+
+    let current = match unit(index) {
+        Some(current) => current,
+        None => return repl_char,
+    };
+    if is_low_surrogate(current) {
+        let is_paired = index >= 2 && unit(index - 2).is_some_and(is_high_surrogate);
+        return if is_paired { filler } else { repl_char };
+    }
+
+    let units = [current, unit(index + 2).unwrap_or(0)];
+    match std::char::decode_utf16(units.iter().cloned()).next() {
+        Some(Ok(ch)) if !ch.is_control() => single_column(ch, repl_char),
+        _ => repl_char,
+    }
+}
+
+/// Decodes the sequence at the start of `bytes` and returns its character and length.
+fn decode_utf8(bytes: &[u8]) -> Option<(char, usize)> {
+    let len = match bytes[0] {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF7 => 4,
+        _ => return None,
+    };
+    let sequence = std::str::from_utf8(bytes.get(..len)?).ok()?;
+    sequence.chars().next().map(|ch| (ch, len))
 }
 
 #[cfg(test)]
@@ -111,4 +304,99 @@ mod tests {
 
         assert_eq!(CODEPAGE_ASCII, &*codepage);
     }
+
+    /// Converts the published mapping of an EBCDIC code page to its ISO-8859-1 code points into
+    /// a codepage, using the same conventions as CODEPAGE_1252 for the non-printable characters.
+    fn generate_latin1_codepage(code_points: &[u8]) -> Vec<char> {
+        code_points.iter()
+            .map(|&code_point| match code_point {
+                0x00..=0x1F | 0x7F..=0x9F | 0xAD => super::NIL,
+                0xA0 => ' ',
+                _ => code_point as char,
+            })
+            .collect()
+    }
+
+    fn generate_variant(base: &[char], differences: &[(usize, char)]) -> Vec<char> {
+        let mut codepage = base.to_vec();
+        for &(byte, ch) in differences {
+            codepage[byte] = ch;
+        }
+        codepage
+    }
+
+    fn generate_cp037() -> Vec<char> {
+        // The EBCDIC to ISO-8859-1 mapping as published by IBM for CCSID 37
+        generate_latin1_codepage(&[
+            //0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+            0x00, 0x01, 0x02, 0x03, 0x9C, 0x09, 0x86, 0x7F, 0x97, 0x8D, 0x8E, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F, // 0
+            0x10, 0x11, 0x12, 0x13, 0x9D, 0x85, 0x08, 0x87, 0x18, 0x19, 0x92, 0x8F, 0x1C, 0x1D, 0x1E, 0x1F, // 1
+            0x80, 0x81, 0x82, 0x83, 0x84, 0x0A, 0x17, 0x1B, 0x88, 0x89, 0x8A, 0x8B, 0x8C, 0x05, 0x06, 0x07, // 2
+            0x90, 0x91, 0x16, 0x93, 0x94, 0x95, 0x96, 0x04, 0x98, 0x99, 0x9A, 0x9B, 0x14, 0x15, 0x9E, 0x1A, // 3
+            0x20, 0xA0, 0xE2, 0xE4, 0xE0, 0xE1, 0xE3, 0xE5, 0xE7, 0xF1, 0xA2, 0x2E, 0x3C, 0x28, 0x2B, 0x7C, // 4
+            0x26, 0xE9, 0xEA, 0xEB, 0xE8, 0xED, 0xEE, 0xEF, 0xEC, 0xDF, 0x21, 0x24, 0x2A, 0x29, 0x3B, 0xAC, // 5
+            0x2D, 0x2F, 0xC2, 0xC4, 0xC0, 0xC1, 0xC3, 0xC5, 0xC7, 0xD1, 0xA6, 0x2C, 0x25, 0x5F, 0x3E, 0x3F, // 6
+            0xF8, 0xC9, 0xCA, 0xCB, 0xC8, 0xCD, 0xCE, 0xCF, 0xCC, 0x60, 0x3A, 0x23, 0x40, 0x27, 0x3D, 0x22, // 7
+            0xD8, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69, 0xAB, 0xBB, 0xF0, 0xFD, 0xFE, 0xB1, // 8
+            0xB0, 0x6A, 0x6B, 0x6C, 0x6D, 0x6E, 0x6F, 0x70, 0x71, 0x72, 0xAA, 0xBA, 0xE6, 0xB8, 0xC6, 0xA4, // 9
+            0xB5, 0x7E, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7A, 0xA1, 0xBF, 0xD0, 0xDD, 0xDE, 0xAE, // A
+            0x5E, 0xA3, 0xA5, 0xB7, 0xA9, 0xA7, 0xB6, 0xBC, 0xBD, 0xBE, 0x5B, 0x5D, 0xAF, 0xA8, 0xB4, 0xD7, // B
+            0x7B, 0x41, 0x42, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49, 0xAD, 0xF4, 0xF6, 0xF2, 0xF3, 0xF5, // C
+            0x7D, 0x4A, 0x4B, 0x4C, 0x4D, 0x4E, 0x4F, 0x50, 0x51, 0x52, 0xB9, 0xFB, 0xFC, 0xF9, 0xFA, 0xFF, // D
+            0x5C, 0xF7, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58, 0x59, 0x5A, 0xB2, 0xD4, 0xD6, 0xD2, 0xD3, 0xD5, // E
+            0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0xB3, 0xDB, 0xDC, 0xD9, 0xDA, 0x9F, // F
+        ])
+    }
+
+    #[test]
+    fn test_hardcoded_cp037_table_matches_the_generated_one() {
+        assert_eq!(CODEPAGE_0037, &*generate_cp037());
+    }
+
+    #[test]
+    fn test_hardcoded_cp500_table_matches_the_generated_one() {
+        // The published differences between CCSID 500 and CCSID 37
+        let codepage = generate_variant(&generate_cp037(), &[
+            (0x4A, '['), (0x4F, '!'), (0x5A, ']'), (0x5F, '^'), (0xB0, '¢'), (0xBA, '¬'), (0xBB, '|'),
+        ]);
+
+        assert_eq!(CODEPAGE_0500, &*codepage);
+    }
+
+    #[test]
+    fn test_hardcoded_cp1047_table_matches_the_generated_one() {
+        // The published differences between CCSID 1047 and CCSID 37
+        let codepage = generate_variant(&generate_cp037(), &[
+            (0x5F, '^'), (0xAD, '['), (0xB0, '¬'), (0xBA, 'Ý'), (0xBB, '¨'), (0xBD, ']'),
+        ]);
+
+        assert_eq!(CODEPAGE_1047, &*codepage);
+    }
+
+    #[test]
+    fn test_hardcoded_cp273_table_matches_the_generated_one() {
+        // The published differences between CCSID 273 and CCSID 37
+        let codepage = generate_variant(&generate_cp037(), &[
+            (0x43, '{'), (0x4A, 'Ä'), (0x4F, '!'), (0x59, '~'), (0x5A, 'Ü'), (0x5F, '^'), (0x63, '['),
+            (0x6A, 'ö'), (0x7C, '§'), (0xA1, 'ß'), (0xB0, '¢'), (0xB5, '@'), (0xBA, '¬'), (0xBB, '|'),
+            (0xBC, '‾'), (0xC0, 'ä'), (0xCC, '¦'), (0xD0, 'ü'), (0xDC, '}'), (0xE0, 'Ö'), (0xEC, '\\'),
+            (0xFC, ']'),
+        ]);
+
+        assert_eq!(CODEPAGE_0273, &*codepage);
+    }
+
+    #[test]
+    fn test_ebcdic_tables_map_the_alphanumerics_to_the_same_bytes() {
+        for codepage in &[CODEPAGE_0037, CODEPAGE_0500, CODEPAGE_1047, CODEPAGE_0273] {
+            for (i, ch) in ('a'..='i').chain('j'..='r').chain('s'..='z').enumerate() {
+                let byte = [0x81..0x8A, 0x91..0x9A, 0xA2..0xAA].iter().flat_map(|range| range.clone()).nth(i).unwrap();
+                assert_eq!(as_char(byte, codepage, '.'), ch);
+                assert_eq!(as_char(byte + 0x40, codepage, '.'), ch.to_ascii_uppercase());
+            }
+            for (i, ch) in ('0'..='9').enumerate() {
+                assert_eq!(as_char(0xF0 + i as u8, codepage, '.'), ch);
+            }
+        }
+    }
 }
@@ -1,6 +1,9 @@
 //! Provides helpers for generating colors for use in HexViewBuilder printing,
 //! as well as some reexports of the underlying color crate, `termcolor`
 
+use std::cell::Cell;
+use std::cmp;
+use std::collections::BTreeSet;
 use std::io::{self, Write};
 use std::ops::Range;
 
@@ -10,6 +13,10 @@ pub use termcolor::Color as Color;
 pub use termcolor::ColorSpec as Spec;
 
 /// A vector of `(ColorSpec, Range)` values to print
+///
+/// Overlapping ranges are layered: wider ranges lie below narrower ones, and of two ranges with the
+/// same length the one added last lies on top. Each layer only overrides the attributes it sets,
+/// so a bold range containing a red range results in bold red for the bytes of the red range.
 pub type Colors = Vec<(Spec, Range<usize>)>;
 
 pub struct ColorlessString(pub String);
@@ -38,45 +45,152 @@ impl WriteColor for ColorlessString {
     }
 }
 
-pub struct ColorRange<'a> {
-    colors: &'a Colors,
-    offset: usize,
+/// The layers of a `Colors` vector, flattened into disjoint segments that carry their composed spec
+/// and the indices of the ranges covering them.
+#[derive(Clone, Default)]
+pub struct ColorIndex {
+    segments: Vec<(Range<usize>, Spec, Vec<usize>)>,
 }
 
-impl<'a> Clone for ColorRange<'a> {
-    fn clone(&self) -> Self {
-        ColorRange {
-            colors: self.colors,
-            offset: self.offset,
+impl ColorIndex {
+    /// Flattens the layers of `colors` by sweeping over the boundaries of their ranges, keeping
+    /// track of the layers covering each segment.
+    pub fn new(colors: &Colors) -> Self {
+        let mut layers: Vec<usize> = (0..colors.len()).filter(|&i| !colors[i].1.is_empty()).collect();
+        layers.sort_by_key(|&i| cmp::Reverse(colors[i].1.len()));
+
+        let mut starts = layers.clone();
+        starts.sort_by_key(|&i| colors[i].1.start);
+        let mut ends = layers.clone();
+        ends.sort_by_key(|&i| colors[i].1.end);
+        let mut boundaries: Vec<usize> = colors.iter().flat_map(|(_, range)| vec![range.start, range.end]).collect();
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        let mut level = vec![0; colors.len()];
+        for (n, &i) in layers.iter().enumerate() {
+            level[i] = n;
         }
+
+        let mut active = BTreeSet::new();
+        let mut starts = starts.into_iter().peekable();
+        let mut ends = ends.into_iter().peekable();
+        let mut segments: Vec<(Range<usize>, Spec, Vec<usize>)> = Vec::new();
+
+        for window in boundaries.windows(2) {
+            let (begin, end) = (window[0], window[1]);
+            while let Some(i) = ends.next_if(|&i| colors[i].1.end <= begin) {
+                active.remove(&level[i]);
+            }
+            while let Some(i) = starts.next_if(|&i| colors[i].1.start <= begin) {
+                active.insert(level[i]);
+            }
+
+            let mut spec: Option<Spec> = None;
+            for &n in &active {
+                let above = &colors[layers[n]].0;
+                spec = Some(match spec {
+                    Some(below) => compose(&below, above),
+                    None => above.clone(),
+                });
+            }
+
+            if let Some(spec) = spec {
+                let mut covering: Vec<usize> = active.iter().map(|&n| layers[n]).collect();
+                covering.sort_unstable();
+                match segments.last_mut() {
+                    Some(&mut (ref mut range, ref previous, ref previous_covering)) if range.end == begin && *previous == spec && *previous_covering == covering => range.end = end,
+                    _ => segments.push((begin..end, spec, covering)),
+                }
+            }
+        }
+
+        ColorIndex { segments }
     }
 }
 
+/// Looks up the composed color of the bytes of a row.
+///
+/// The lookup keeps a cursor into the [ColorIndex](struct.ColorIndex.html), so looking up the bytes
+/// in ascending order takes amortized constant time.
+#[derive(Clone)]
+pub struct ColorRange<'a> {
+    index: &'a ColorIndex,
+    offset: usize,
+    cursor: Cell<usize>,
+}
+
 impl<'a> ColorRange<'a> {
-    pub fn new(colors: &'a Colors) -> Self {
+    /// Constructs a new ColorRange looking up the composed colors of `index`.
+    pub fn new(index: &'a ColorIndex) -> Self {
         ColorRange {
-            colors: colors,
+            index,
             offset: 0,
+            cursor: Cell::new(0),
         }
     }
+    /// Sets the offset of the looked up bytes within the colored data. The offset wraps around,
+    /// so bytes before the start of the colored data are looked up without a color.
     pub fn update_offset(&mut self, offset: usize) {
         self.offset = offset;
     }
-    pub fn get(&self, idx: usize) -> Option<&Spec> {
-        let mut i = 0;
-        let offset = self.offset + idx;
-        while i < self.colors.len() {
-            let (ref rgb, ref range) = self.colors[i];
-            if offset >= range.start && offset < range.end {
-                return Some(rgb);
-            } else {
-                i += 1;
-            }
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+    /// Returns the composition of all color layers at `idx`, relative to the current offset.
+    pub fn get(&self, idx: usize) -> Option<&'a Spec> {
+        self.segment(idx).map(|(_, spec, _)| spec)
+    }
+    /// Returns the indices, in ascending order, of the color ranges covering `idx`, relative to the
+    /// current offset.
+    pub(crate) fn layers(&self, idx: usize) -> &'a [usize] {
+        self.segment(idx).map_or(&[], |(_, _, covering)| covering)
+    }
+    fn segment(&self, idx: usize) -> Option<&'a (Range<usize>, Spec, Vec<usize>)> {
+        let offset = self.offset.wrapping_add(idx);
+        let segments = &self.index.segments;
+
+        // The cursor points to the first segment that ends after the offset
+        let mut cursor = self.cursor.get();
+        while cursor > 0 && segments[cursor - 1].0.end > offset {
+            cursor -= 1;
+        }
+        while cursor < segments.len() && segments[cursor].0.end <= offset {
+            cursor += 1;
+        }
+        self.cursor.set(cursor);
+
+        match segments.get(cursor) {
+            Some(segment) if segment.0.start <= offset => Some(segment),
+            _ => None,
         }
-        None
     }
 }
 
+/// Sorts the colors from the bottom layer to the top layer: wider ranges first, and ranges of the
+/// same length in the order in which they were added.
+pub fn sort_into_layers(colors: &mut Colors) {
+    colors.sort_by_key(|(_, range)| cmp::Reverse(range.len()));
+}
+
+/// Lays `above` on top of `below`: the colors set in `above` replace the ones of `below`, and the
+/// attributes enabled in either of them are enabled in the result.
+fn compose(below: &Spec, above: &Spec) -> Spec {
+    let mut spec = below.clone();
+    if let Some(fg) = above.fg() {
+        spec.set_fg(Some(*fg));
+    }
+    if let Some(bg) = above.bg() {
+        spec.set_bg(Some(*bg));
+    }
+    spec.set_bold(below.bold() || above.bold())
+        .set_italic(below.italic() || above.italic())
+        .set_underline(below.underline() || above.underline())
+        .set_dimmed(below.dimmed() || above.dimmed())
+        .set_intense(below.intense() || above.intense());
+    spec
+}
+
 macro_rules! make_color {
     ($name:ident, $name_bold:ident, $color:ident) => {
         /// Creates the appropriate ColorSpec
@@ -98,3 +212,89 @@ make_color!(magenta, magenta_bold, Magenta);
 make_color!(black, black_bold, Black);
 make_color!(cyan, cyan_bold, Cyan);
 make_color!(white, white_bold, White);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_without_color_have_no_spec() {
+        let index = ColorIndex::new(&vec![(red(), 2..4)]);
+        let color_range = ColorRange::new(&index);
+
+        assert_eq!(color_range.get(1), None);
+        assert_eq!(color_range.get(4), None);
+    }
+
+    #[test]
+    fn nested_ranges_compose_their_specs() {
+        let bold = Spec::new().set_bold(true).clone();
+        let index = ColorIndex::new(&vec![(bold.clone(), 0..10), (red(), 2..4)]);
+        let color_range = ColorRange::new(&index);
+
+        assert_eq!(color_range.get(0), Some(&bold));
+        assert_eq!(color_range.get(2), Some(&red_bold()));
+        assert_eq!(color_range.get(4), Some(&bold));
+    }
+
+    #[test]
+    fn the_innermost_range_wins_regardless_of_the_order_of_addition() {
+        let index = ColorIndex::new(&vec![(green(), 4..5), (blue(), 3..7), (red(), 0..10)]);
+        let color_range = ColorRange::new(&index);
+
+        assert_eq!(color_range.get(0), Some(&red()));
+        assert_eq!(color_range.get(3), Some(&blue()));
+        assert_eq!(color_range.get(4), Some(&green()));
+    }
+
+    #[test]
+    fn the_last_added_range_wins_for_ranges_of_the_same_length() {
+        let index = ColorIndex::new(&vec![(red(), 0..4), (blue(), 2..6)]);
+        let color_range = ColorRange::new(&index);
+
+        assert_eq!(color_range.get(1), Some(&red()));
+        assert_eq!(color_range.get(2), Some(&blue()));
+        assert_eq!(color_range.get(5), Some(&blue()));
+    }
+
+    #[test]
+    fn the_background_of_an_outer_range_shows_through() {
+        let background = Spec::new().set_bg(Some(Color::Yellow)).clone();
+        let index = ColorIndex::new(&vec![(background, 0..8), (red(), 2..4)]);
+        let color_range = ColorRange::new(&index);
+
+        assert_eq!(color_range.get(3), Some(&red().set_bg(Some(Color::Yellow)).clone()));
+    }
+
+    #[test]
+    fn the_offset_is_added_to_the_index() {
+        let index = ColorIndex::new(&vec![(red(), 18..20)]);
+        let mut color_range = ColorRange::new(&index);
+        color_range.update_offset(16);
+
+        assert_eq!(color_range.get(1), None);
+        assert_eq!(color_range.get(2), Some(&red()));
+    }
+
+    #[test]
+    fn the_index_matches_composing_all_layers_in_any_lookup_order() {
+        let specs = [red(), blue_bold(), green(), Spec::new().set_underline(true).clone()];
+        let colors: Colors = (0..60usize)
+            .map(|n| (specs[n % specs.len()].clone(), (n * 37) % 101..(n * 37) % 101 + (n * 13) % 17))
+            .collect();
+
+        let mut layered = colors.clone();
+        sort_into_layers(&mut layered);
+        let expected = |offset: usize| layered.iter()
+            .filter(|&(_, range)| range.start <= offset && offset < range.end)
+            .fold(None, |below: Option<Spec>, (above, _)| Some(below.map_or(above.clone(), |below| compose(&below, above))));
+
+        let index = ColorIndex::new(&colors);
+        let color_range = ColorRange::new(&index);
+        let offsets: Vec<usize> = (0..130).chain((0..130).rev()).chain((0..130).map(|n| n * 7 % 130)).collect();
+
+        for offset in offsets {
+            assert_eq!(color_range.get(offset).cloned(), expected(offset), "offset {}", offset);
+        }
+    }
+}
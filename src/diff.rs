@@ -0,0 +1,311 @@
+use std;
+use std::cmp;
+use std::io;
+use std::ops::Range;
+use atty;
+
+use termcolor::{BufferWriter, Buffer, ColorChoice, WriteColor};
+
+use color::{self, Spec, ColorlessString};
+use format::{self, HexView, HexViewBuilder, Padding};
+use byte_mapping;
+
+/// The HexDiffView struct displays two byte slices next to each other and highlights the bytes
+/// in which they differ.
+pub struct HexDiffView<'a> {
+    left: HexView<'a>,
+    right: HexView<'a>,
+    address_offset: usize,
+    row_width: usize,
+    differences: Vec<Range<usize>>,
+    collapse_identical_rows: bool,
+    force_color: bool,
+}
+
+impl<'a> HexDiffView<'a> {
+    /// Prints the diff to stdout, with the differing bytes in color.
+    pub fn print(&self) -> io::Result<()> {
+        let cc = if self.force_color || atty::is(atty::Stream::Stdout) { ColorChoice::Auto } else { ColorChoice::Never };
+        let writer = BufferWriter::stdout(cc);
+        let mut buffer: Buffer = writer.buffer();
+        self.fmt(&mut buffer)?;
+        writer.print(&buffer)?;
+        Ok(())
+    }
+
+    /// Returns the ranges, relative to the start of the slices, in which both slices differ.
+    ///
+    /// If one slice is longer than the other, its excess bytes are reported as a difference.
+    pub fn differences(&self) -> &[Range<usize>] {
+        &self.differences
+    }
+
+    pub fn fmt<W: WriteColor>(&self, buffer: &mut W) -> io::Result<()> {
+        let begin_padding = format::calculate_begin_padding(self.address_offset, self.row_width);
+        let total = begin_padding + cmp::max(self.left.data.len(), self.right.data.len());
+        let row_count = cmp::max(1, (total + self.row_width - 1) / self.row_width);
+        let mut left_color_range = self.left.color_range();
+        let mut right_color_range = self.right.color_range();
+        let mut separator = "";
+        let mut collapsing = false;
+
+        for row in 0..row_count {
+            let address = self.address_offset - begin_padding + row * self.row_width;
+            let left = format::row_at(self.left.data, begin_padding, self.row_width, row);
+            let right = format::row_at(self.right.data, begin_padding, self.row_width, row);
+
+            // The first and the last row are always written, so the extent of the data stays visible
+            let is_edge_row = row == 0 || row + 1 == row_count;
+            if self.collapse_identical_rows && !is_edge_row && self.is_identical_row(begin_padding, row) {
+                if !collapsing {
+                    write!(buffer, "{}*", separator)?;
+                    collapsing = true;
+                    separator = "\n";
+                }
+                continue;
+            }
+            collapsing = false;
+
+            write!(buffer, "{}", separator)?;
+            self.fmt_side(buffer, &self.left, address, left, &mut left_color_range)?;
+            write!(buffer, "  ")?;
+            self.fmt_side(buffer, &self.right, address, right, &mut right_color_range)?;
+            separator = "\n";
+        }
+
+        write!(buffer, "{}", separator)?;
+        self.fmt_summary(buffer)
+    }
+
+    fn is_identical_row(&self, begin_padding: usize, row: usize) -> bool {
+        let begin = (row * self.row_width).saturating_sub(begin_padding);
+        let end = (row + 1) * self.row_width - begin_padding;
+        !self.differences.iter().any(|range| range.start < end && begin < range.end)
+    }
+
+    fn fmt_side<W: WriteColor>(&self, buffer: &mut W, view: &HexView, address: usize, row: Option<(Range<usize>, Padding)>, color_range: &mut color::ColorRange) -> io::Result<()> {
+        match row {
+            Some((range, padding)) => {
+                color_range.update_offset(range.start);
+                format::fmt_line(buffer, view, address, range.start, view.data, range, color_range, &padding)
+            },
+            None => {
+                let mut blank = ColorlessString(String::new());
+                format::fmt_line(&mut blank, view, address, 0, &[], 0..0, color_range, &Padding::new(self.row_width, 0))?;
+                write!(buffer, "{:width$}", "", width = blank.0.chars().count())
+            },
+        }
+    }
+
+    fn fmt_summary<W: WriteColor>(&self, buffer: &mut W) -> io::Result<()> {
+        if self.differences.is_empty() {
+            return write!(buffer, "No differences");
+        }
+
+        write!(buffer, "Differing ranges:")?;
+        let mut separator = " ";
+        for range in &self.differences {
+            write!(buffer, "{}{:X}..{:X}", separator, self.address_offset + range.start, self.address_offset + range.end)?;
+            separator = ", ";
+        }
+        Ok(())
+    }
+}
+
+/// A builder for the [HexDiffView](struct.HexDiffView.html) struct.
+pub struct HexDiffViewBuilder<'a> {
+    left: &'a [u8],
+    right: &'a [u8],
+    address_offset: usize,
+    codepage: &'a [char],
+    replacement_character: char,
+    row_width: usize,
+    diff_color: Spec,
+    collapse_identical_rows: bool,
+    force_color: bool,
+}
+
+impl<'a> HexDiffViewBuilder<'a> {
+    /// Constructs a new HexDiffViewBuilder comparing the `left` with the `right` data.
+    pub fn new(left: &'a [u8], right: &'a [u8]) -> HexDiffViewBuilder<'a> {
+        HexDiffViewBuilder {
+            left,
+            right,
+            address_offset: 0,
+            codepage: byte_mapping::CODEPAGE_0850,
+            replacement_character: '.',
+            row_width: 16,
+            diff_color: color::red_bold(),
+            collapse_identical_rows: false,
+            force_color: false,
+        }
+    }
+
+    /// Configures the address offset of both sides of the HexDiffView under construction.
+    pub fn address_offset(mut self, offset: usize) -> HexDiffViewBuilder<'a> {
+        self.address_offset = offset;
+        self
+    }
+
+    /// Forces the differences to be printed in color in `print`, even if redirected to a file or pipe.
+    pub fn force_color(mut self) -> Self {
+        self.force_color = true;
+        self
+    }
+
+    /// Configures the codepage of both sides of the HexDiffView under construction.
+    pub fn codepage<'b: 'a>(mut self, codepage: &'b [char]) -> HexDiffViewBuilder<'a> {
+        self.codepage = codepage;
+        self
+    }
+
+    /// Configures the replacement character of both sides of the HexDiffView under construction.
+    pub fn replacement_character(mut self, ch: char) -> HexDiffViewBuilder<'a> {
+        self.replacement_character = ch;
+        self
+    }
+
+    /// Configures the row width of both sides of the HexDiffView under construction.
+    pub fn row_width(mut self, width: usize) -> HexDiffViewBuilder<'a> {
+        self.row_width = width;
+        self
+    }
+
+    /// Configures the color used to highlight differing bytes. The default is bold red.
+    pub fn diff_color(mut self, spec: Spec) -> HexDiffViewBuilder<'a> {
+        self.diff_color = spec;
+        self
+    }
+
+    /// Configures whether runs of rows without any difference are collapsed into a single `*` line.
+    /// The first and the last row are never collapsed.
+    pub fn collapse_identical_rows(mut self, collapse: bool) -> HexDiffViewBuilder<'a> {
+        self.collapse_identical_rows = collapse;
+        self
+    }
+
+    /// Constructs the HexDiffView.
+    pub fn finish(self) -> HexDiffView<'a> {
+        let differences = calculate_differences(self.left, self.right);
+        let colors: color::Colors = differences.iter().map(|range| (self.diff_color.clone(), range.clone())).collect();
+
+        let side = |data| HexViewBuilder::new(data)
+            .address_offset(self.address_offset)
+            .codepage(self.codepage)
+            .replacement_character(self.replacement_character)
+            .row_width(self.row_width)
+            .add_colors(colors.clone())
+            .finish();
+
+        HexDiffView {
+            left: side(self.left),
+            right: side(self.right),
+            address_offset: self.address_offset,
+            row_width: self.row_width,
+            differences,
+            collapse_identical_rows: self.collapse_identical_rows,
+            force_color: self.force_color,
+        }
+    }
+}
+
+fn calculate_differences(left: &[u8], right: &[u8]) -> Vec<Range<usize>> {
+    let mut differences: Vec<Range<usize>> = Vec::new();
+    let len = cmp::max(left.len(), right.len());
+
+    for i in 0..len {
+        if left.get(i) == right.get(i) {
+            continue;
+        }
+        match differences.last_mut() {
+            Some(range) if range.end == i => range.end += 1,
+            _ => differences.push(i..i + 1),
+        }
+    }
+    differences
+}
+
+impl<'a> std::fmt::Display for HexDiffView<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.row_width == 0 {
+            write!(f, "Invalid HexDiffView::width")?;
+            return Err(std::fmt::Error);
+        }
+        let mut string = ColorlessString(String::new());
+        match self.fmt(&mut string) {
+            Ok(()) => {
+                write!(f, "{}", string.0)
+            },
+            Err(e) => write!(f, "{}", e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn differing_bytes_are_summarized() {
+        let left = [0x41, 0x42, 0x43, 0x44, 0x45, 0x46];
+        let right = [0x41, 0x58, 0x58, 0x44, 0x45, 0x58, 0x47];
+
+        let diff = HexDiffViewBuilder::new(&left, &right)
+            .address_offset(0x10)
+            .row_width(4)
+            .finish();
+
+        assert_eq!(diff.differences(), &[1..3, 5..7]);
+
+        let result = format!("{}", diff);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("00000010  41 42 43 44  | ABCD |  00000010  41 58 58 44  | AXXD |"));
+        assert_eq!(lines.next(), Some("00000014  45 46        | EF   |  00000014  45 58 47     | EXG  |"));
+        assert_eq!(lines.next(), Some("Differing ranges: 11..13, 15..17"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn the_shorter_side_is_left_blank() {
+        let left = [0x41; 2];
+        let right = [0x41; 6];
+
+        let result = format!("{}", HexDiffViewBuilder::new(&left, &right).row_width(4).finish());
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("00000000  41 41        | AA   |  00000000  41 41 41 41  | AAAA |"));
+        assert_eq!(lines.next(), Some("                                 00000004  41 41        | AA   |"));
+        assert_eq!(lines.next(), Some("Differing ranges: 2..6"));
+    }
+
+    #[test]
+    fn identical_rows_can_be_collapsed() {
+        let left = [0x41; 20];
+        let mut right = [0x41; 20];
+        right[17] = 0x42;
+
+        let result = format!("{}", HexDiffViewBuilder::new(&left, &right)
+            .address_offset(2)
+            .row_width(4)
+            .collapse_identical_rows(true)
+            .finish());
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("00000000        41 41  |   AA |  00000000        41 41  |   AA |"));
+        assert_eq!(lines.next(), Some("*"));
+        assert_eq!(lines.next(), Some("00000010  41 41 41 41  | AAAA |  00000010  41 41 41 42  | AAAB |"));
+        assert_eq!(lines.next(), Some("00000014  41 41        | AA   |  00000014  41 41        | AA   |"));
+        assert_eq!(lines.next(), Some("Differing ranges: 13..14"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn identical_data_has_no_differences() {
+        let data = [0x41; 4];
+
+        let result = format!("{}", HexDiffViewBuilder::new(&data, &data).finish());
+
+        assert!(result.ends_with("\nNo differences"));
+    }
+}
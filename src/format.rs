@@ -1,24 +1,183 @@
 use std::ops::Range;
 use std;
-use std::io;
+use std::cmp;
+use std::io::{self, Write};
 use atty;
 
 use termcolor::{BufferWriter, Buffer, Color, ColorChoice, WriteColor};
 
-use color::{Spec, Colors, ColorRange, ColorlessString};
+use color::{Spec, Colors, ColorIndex, ColorRange, ColorlessString};
 use byte_mapping;
 
 /// The HexView struct represents the configuration of how to display the data.
 pub struct HexView<'a> {
-    address_offset: usize,
-    codepage: &'a [char],
-    data: &'a [u8],
-    replacement_character: char,
-    row_width: usize,
-    colors: Colors,
-    force_color: bool,
+    pub(crate) address_offset: usize,
+    pub(crate) codepage: &'a [char],
+    pub(crate) data: &'a [u8],
+    pub(crate) replacement_character: char,
+    pub(crate) row_width: usize,
+    pub(crate) colors: Colors,
+    color_index: ColorIndex,
+    pub(crate) force_color: bool,
+    pub(crate) group_size: usize,
+    pub(crate) byte_order: Endian,
+    pub(crate) squeeze_repeats: bool,
+    annotations: Vec<Annotation>,
+    annotation_margin: bool,
+    pub(crate) char_encoding: CharEncoding,
+    pub(crate) continuation_character: char,
+    pub(crate) radix: Radix,
+    pub(crate) lowercase_hex: bool,
+    pub(crate) byte_prefix: String,
+    pub(crate) byte_suffix: String,
+    pub(crate) address_digits: Option<usize>,
+    pub(crate) address_radix: Radix,
+    address_prefix: String,
+    column_header: bool,
+    column_header_color: Option<Spec>,
+    column_separator_every: usize,
+    column_separator: String,
+    panes: Vec<Pane>,
+    pane_separator: String,
+    pane_delimiters: Vec<(Pane, String, String)>,
 }
 
+/// A labeled color range, see [annotate](struct.HexViewBuilder.html#method.annotate).
+struct Annotation {
+    label: String,
+    spec: Spec,
+    range: Range<usize>,
+}
+
+/// The order in which the bytes of a group are displayed in the hex pane.
+///
+/// See [group_size](struct.HexViewBuilder.html#method.group_size) and
+/// [byte_order](struct.HexViewBuilder.html#method.byte_order). Also selects the byte order of
+/// `CharEncoding::Utf16`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endian {
+    /// The least significant byte of a group is displayed last, i.e. the bytes are shown in memory order.
+    Big,
+    /// The least significant byte of a group is displayed first, like `xxd -e` does.
+    Little,
+}
+
+/// The radix in which the bytes are written in the hex pane.
+///
+/// See [radix](struct.HexViewBuilder.html#method.radix).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Radix {
+    /// Two uppercase hexadecimal digits per byte.
+    Hexadecimal,
+    /// Three octal digits per byte, like `od -b` does.
+    Octal,
+    /// Three decimal digits per byte.
+    Decimal,
+    /// Eight binary digits per byte.
+    Binary,
+}
+
+impl Radix {
+    fn base(self) -> usize {
+        match self {
+            Radix::Hexadecimal => 16,
+            Radix::Octal => 8,
+            Radix::Decimal => 10,
+            Radix::Binary => 2,
+        }
+    }
+
+    /// Returns the number of digits of a byte written in this radix.
+    pub(crate) fn digits(self) -> usize {
+        match self {
+            Radix::Hexadecimal => 2,
+            Radix::Octal | Radix::Decimal => 3,
+            Radix::Binary => 8,
+        }
+    }
+
+    /// Returns the number of digits needed to write `number` in this radix.
+    pub(crate) fn digits_of(self, mut number: usize) -> usize {
+        let mut digits = 1;
+        while number >= self.base() {
+            number /= self.base();
+            digits += 1;
+        }
+        digits
+    }
+
+    /// Writes the byte with leading zeros up to the number of digits of this radix, with the
+    /// hexadecimal digits in lowercase if `lowercase` is set.
+    pub(crate) fn fmt_byte<W: Write>(self, f: &mut W, byte: u8, lowercase: bool) -> io::Result<()> {
+        let symbols = if lowercase { b"0123456789abcdef" } else { b"0123456789ABCDEF" };
+        let (base, len) = (self.base(), self.digits());
+        let mut digits = [0; 8];
+        let mut value = byte as usize;
+        for digit in digits[..len].iter_mut().rev() {
+            *digit = symbols[value % base];
+            value /= base;
+        }
+        f.write_all(&digits[..len])
+    }
+
+    /// Writes `number` with leading zeros up to the given number of digits.
+    pub(crate) fn fmt_number(self, number: usize, digits: usize) -> String {
+        match self {
+            Radix::Hexadecimal => format!("{:01$X}", number, digits),
+            Radix::Octal => format!("{:01$o}", number, digits),
+            Radix::Decimal => format!("{:01$}", number, digits),
+            Radix::Binary => format!("{:01$b}", number, digits),
+        }
+    }
+}
+
+/// The way the bytes are decoded into the characters of the char pane.
+///
+/// See [char_encoding](struct.HexViewBuilder.html#method.char_encoding).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CharEncoding {
+    /// Every byte is mapped on its own through the codepage of the HexView.
+    Codepage,
+    /// The bytes are decoded as UTF-8. A character is shown on the lead byte of its sequence and
+    /// its continuation bytes show the continuation character. Characters that do not take a
+    /// single column, like the wide CJK characters, emoji and combining marks, show the
+    /// replacement character, so the columns stay aligned.
+    Utf8,
+    /// The bytes are decoded as UTF-16 code units in the given byte order, starting at the first
+    /// byte of the data. Every code unit takes a single column, so the char pane is half as wide
+    /// as the row, and the second half of a surrogate pair shows the continuation character.
+    /// Like with `Utf8`, characters that do not take a single column show the replacement character.
+    Utf16(Endian),
+}
+
+impl CharEncoding {
+    /// Returns the number of bytes that share a column of the char pane.
+    pub(crate) fn bytes_per_column(self) -> usize {
+        match self {
+            CharEncoding::Utf16(_) => 2,
+            _ => 1,
+        }
+    }
+}
+
+/// A pane of the rows of a HexView.
+///
+/// See [panes](struct.HexViewBuilder.html#method.panes).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pane {
+    /// The address of the row.
+    Address,
+    /// The offset of the first byte of the row within the data.
+    RelativeAddress,
+    /// The bytes of the row, written in the configured radix.
+    Hex,
+    /// The bytes of the row, decoded into characters.
+    Char,
+}
+
+/// The number of bytes around a row that are needed to decode the characters crossing its boundaries.
+pub(crate) const CHAR_CONTEXT: usize = 3;
+
 macro_rules! color {
     ($fmt:ident, $color:ident, $str:expr) => ({
     $fmt.set_color(&$color)?;
@@ -40,56 +199,158 @@ impl<'a> HexView<'a> {
 
     /// Constructs a new HexView for the given data without offset and using codepage 850, a row width
     /// of 16 and `.` as replacement character.
-    pub fn new(data: &[u8]) -> HexView {
+    pub fn new(data: &'a [u8]) -> HexView<'a> {
         HexView {
             address_offset: 0,
-            codepage: &byte_mapping::CODEPAGE_0850,
-            data: data,
+            codepage: byte_mapping::CODEPAGE_0850,
+            data,
             replacement_character: '.',
             row_width: 16,
             colors: Colors::new(),
+            color_index: ColorIndex::default(),
             force_color: false,
+            group_size: 1,
+            byte_order: Endian::Big,
+            squeeze_repeats: false,
+            annotations: Vec::new(),
+            annotation_margin: false,
+            char_encoding: CharEncoding::Codepage,
+            continuation_character: '·',
+            radix: Radix::Hexadecimal,
+            lowercase_hex: false,
+            byte_prefix: String::new(),
+            byte_suffix: String::new(),
+            address_digits: Some(8),
+            address_radix: Radix::Hexadecimal,
+            address_prefix: String::new(),
+            column_header: false,
+            column_header_color: None,
+            column_separator_every: 0,
+            column_separator: " ".to_string(),
+            panes: vec![Pane::Address, Pane::Hex, Pane::Char],
+            pane_separator: "  ".to_string(),
+            pane_delimiters: vec![(Pane::Char, "| ".to_string(), " |".to_string())],
         }
     }
 
-    pub fn fmt<W: WriteColor>(&self, buffer: &mut W) -> io::Result<()> {
-        let begin_padding = calculate_begin_padding(self.address_offset, self.row_width);
-        let end_padding = calculate_end_padding(begin_padding + self.data.len(), self.row_width);
-        let mut address = self.address_offset - begin_padding;
-        let mut offset = 0;
-        let mut color_range = ColorRange::new(&self.colors);
-        let mut separator = "";
+    pub(crate) fn color_range(&self) -> ColorRange<'_> {
+        ColorRange::new(&self.color_index)
+    }
+
+    /// Returns the panes of the rows, in the order in which they are shown.
+    pub(crate) fn panes(&self) -> &[Pane] {
+        &self.panes
+    }
 
-        if self.data.len() + begin_padding + end_padding <= self.row_width {
-            fmt_line(buffer, address, &self.codepage, self.replacement_character, &self.data, &mut color_range, &Padding::new(begin_padding, end_padding))?;
-            return Ok(())
+    /// Returns the strings written before and after the given pane.
+    pub(crate) fn pane_delimiters(&self, pane: Pane) -> (&str, &str) {
+        match self.pane_delimiters.iter().find(|&&(p, _, _)| p == pane) {
+            Some((_, open, close)) => (open, close),
+            None => ("", ""),
         }
+    }
+
+    /// Writes `byte` as configured for the hex pane.
+    pub(crate) fn fmt_byte<W: Write>(&self, f: &mut W, byte: u8) -> io::Result<()> {
+        f.write_all(self.byte_prefix.as_bytes())?;
+        self.radix.fmt_byte(f, byte, self.lowercase_hex)?;
+        f.write_all(self.byte_suffix.as_bytes())
+    }
+
+    /// Returns the number of characters taken by a byte in the hex pane.
+    pub(crate) fn byte_width(&self) -> usize {
+        self.byte_prefix.chars().count() + self.radix.digits() + self.byte_suffix.chars().count()
+    }
+
+    /// Applies the configured case to the digits of the hex pane.
+    fn fmt_digits(&self, digits: String) -> String {
+        if self.lowercase_hex { digits.to_lowercase() } else { digits }
+    }
+
+    /// Writes `address` as configured for the address column.
+    pub(crate) fn fmt_address(&self, address: usize) -> String {
+        let end = self.address_offset + self.data.len();
+        let digits = self.address_digits.unwrap_or_else(|| self.address_radix.digits_of(end));
+        format!("{}{}", self.address_prefix, self.address_radix.fmt_number(address, digits))
+    }
+
+    pub fn fmt<W: WriteColor>(&self, buffer: &mut W) -> io::Result<()> {
+        fmt_header(buffer, self)?;
+        self.fmt_rows(buffer)?;
+        fmt_legend(buffer, self)
+    }
+
+    fn fmt_rows<W: WriteColor>(&self, buffer: &mut W) -> io::Result<()> {
+        self.fmt_rows_of(buffer, self.data, self.address_offset, 0..self.data.len(), 0)
+    }
+
+    /// Writes the rows showing the bytes in `window` of `data` at `address_offset`, whose colors
+    /// are looked up from `color_offset` on. The color offset wraps around, so data that starts
+    /// below the offset of the colors is left uncolored. The configured data and address offset of
+    /// the view are not used. The bytes around the window are used to decode the characters that
+    /// cross its edges, and empty data is written as a single empty row.
+    pub(crate) fn fmt_rows_of<W: WriteColor>(&self, buffer: &mut W, data: &[u8], address_offset: usize, window: Range<usize>, color_offset: usize) -> io::Result<()> {
+        let begin_padding = calculate_begin_padding(address_offset, self.row_width);
+        let mut color_range = self.color_range();
+        let mut squeezer = Squeezer::default();
+        let mut separator = "";
 
-        if begin_padding != 0 {
-            let slice = &self.data[offset..offset + self.row_width - begin_padding];
-            fmt_line(buffer, address, &self.codepage, self.replacement_character, &slice, &mut color_range, &Padding::from_left(begin_padding))?;
-            offset += self.row_width - begin_padding;
-            address += self.row_width;
+        for row in (begin_padding + window.start) / self.row_width.. {
+            let (row_range, row_padding) = match row_at(data, begin_padding, self.row_width, row) {
+                Some((range, padding)) if range.start < window.end || data.is_empty() => (range, padding),
+                _ => break,
+            };
+            let range = cmp::max(row_range.start, window.start)..cmp::min(row_range.end, window.end);
+            let padding = Padding::new(row_padding.left + range.start - row_range.start, row_padding.right + row_range.end - range.end);
+            let address = address_offset - begin_padding + row * self.row_width;
+            color_range.update_offset(color_offset.wrapping_add(range.start));
+
+            match squeezer.squeeze(self, &data[range.clone()], &padding, range.end == window.end, &color_range) {
+                Squeeze::Row => {
+                    write!(buffer, "{}", separator)?;
+                    fmt_line(buffer, self, address, range.start, data, range, &mut color_range, &padding)?;
+                },
+                Squeeze::Start => write!(buffer, "{}*", separator)?,
+                Squeeze::Continue => {},
+            }
             separator = "\n";
-            color_range.update_offset(offset);
         }
+        Ok(())
+    }
 
-        while offset + (self.row_width - 1) < self.data.len() {
-            let slice = &self.data[offset..offset + self.row_width];
-            write!(buffer, "{}", separator)?;
-            fmt_line(buffer, address, &self.codepage, self.replacement_character, &slice, &mut color_range, &Padding::default())?;
-            offset += self.row_width;
-            address += self.row_width;
-            separator = "\n";
-            color_range.update_offset(offset);
+    /// Writes the rows in `rows` of the hextable, numbered like the rows returned by
+    /// [rows](#method.rows), without slicing the data.
+    ///
+    /// The rows are written as they appear in the full hextable, with their absolute addresses,
+    /// colors and annotations. The header, the legend and a line break after the last row are not
+    /// written, and rows past the end of the data are left out. A row width of 0 results in an
+    /// `InvalidInput` error.
+    pub fn render_rows<W: WriteColor>(&self, rows: Range<usize>, buffer: &mut W) -> io::Result<()> {
+        if self.row_width == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Invalid HexView::width"));
+        }
+        let begin_padding = calculate_begin_padding(self.address_offset, self.row_width);
+        let row_count = cmp::max(1, (begin_padding + self.data.len() + self.row_width - 1) / self.row_width);
+        if rows.start >= cmp::min(rows.end, row_count) {
+            return Ok(());
         }
+        let byte_at = |row: usize| cmp::min(self.data.len(), row.saturating_mul(self.row_width).saturating_sub(begin_padding));
+        self.fmt_rows_of(buffer, self.data, self.address_offset, byte_at(rows.start)..byte_at(rows.end), 0)
+    }
 
-        if end_padding != 0 {
-            let slice = &self.data[offset..];
-            writeln!(buffer, "")?;
-            fmt_line(buffer, address, &self.codepage, self.replacement_character, &slice, &mut color_range, &Padding::from_right(end_padding))?;
+    /// Writes the rows showing the bytes in `range` of the data, like [render_rows](#method.render_rows).
+    ///
+    /// The rows keep their alignment, so the rows at the edges of the window are padded where the
+    /// window does not start or end on a row boundary.
+    pub fn render_bytes<W: WriteColor>(&self, range: Range<usize>, buffer: &mut W) -> io::Result<()> {
+        if self.row_width == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Invalid HexView::width"));
         }
-        Ok(())
+        let end = cmp::min(range.end, self.data.len());
+        if range.start >= end {
+            return Ok(());
+        }
+        self.fmt_rows_of(buffer, self.data, self.address_offset, range.start..end, 0)
     }
 }
 
@@ -100,9 +361,9 @@ pub struct HexViewBuilder<'a> {
 
 impl<'a> HexViewBuilder<'a> {
     /// Constructs a new HexViewBuilder for the given data.
-    pub fn new(data: &[u8]) -> HexViewBuilder {
+    pub fn new(data: &'a [u8]) -> HexViewBuilder<'a> {
         HexViewBuilder {
-            hex_view: HexView::new(&data)
+            hex_view: HexView::new(data)
         }
     }
 
@@ -138,6 +399,236 @@ impl<'a> HexViewBuilder<'a> {
         self.hex_view.row_width = width;
         self
     }
+
+    /// Configures the number of bytes that are printed together, without separating whitespace,
+    /// in the hex pane of the HexView under construction.
+    ///
+    /// Groups are aligned to the start of a row, so the row width should be a multiple of the
+    /// group size. The default group size is 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn group_size(mut self, size: usize) -> HexViewBuilder<'a> {
+        assert!(size != 0, "A group size of 0 can not be used to group the bytes");
+        self.hex_view.group_size = size;
+        self
+    }
+
+    /// Configures the order in which the bytes of a group are printed in the hex pane of the
+    /// HexView under construction. The default is `Endian::Big`, which keeps the memory order.
+    pub fn byte_order(mut self, order: Endian) -> HexViewBuilder<'a> {
+        self.hex_view.byte_order = order;
+        self
+    }
+
+    /// Configures whether runs of identical rows are collapsed into a single `*` line, like
+    /// `hexdump -C` does.
+    ///
+    /// Only complete rows are squeezed, so the first and last row are always printed when they
+    /// are padded. A row is only considered a repeat when it is colored the same way as the row
+    /// before it, so a color range starting inside a run of identical rows stays visible.
+    /// The row following a squeezed run is printed with its own address, and the row ending the
+    /// data is never squeezed, so the length of the data can be read from the dump.
+    pub fn squeeze_repeats(mut self, squeeze: bool) -> HexViewBuilder<'a> {
+        self.hex_view.squeeze_repeats = squeeze;
+        self
+    }
+
+    /// Colors the given `range` with `spec` and names it `label`.
+    ///
+    /// The labels of all annotations are listed in a legend below the hextable, in the color of their
+    /// range and followed by the addresses they span. See also [annotation_margin](#method.annotation_margin).
+    pub fn annotate(mut self, range: Range<usize>, label: &str, spec: Spec) -> HexViewBuilder<'a> {
+        self.hex_view.colors.push((spec.clone(), range.clone()));
+        self.hex_view.annotations.push(Annotation {
+            label: label.to_string(),
+            spec,
+            range,
+        });
+        self
+    }
+
+    /// Configures whether the labels of the annotations are also printed to the right of the row
+    /// on which their range starts.
+    pub fn annotation_margin(mut self, margin: bool) -> HexViewBuilder<'a> {
+        self.hex_view.annotation_margin = margin;
+        self
+    }
+
+    /// Configures whether the address column is shown in the HexView under construction. It is
+    /// shown by default.
+    ///
+    /// This is a shorthand for adding `Pane::Address` to the front of the [panes](#method.panes),
+    /// unless they already contain it, or removing it from them.
+    pub fn address_column(mut self, show: bool) -> HexViewBuilder<'a> {
+        let panes = &mut self.hex_view.panes;
+        panes.retain(|&pane| pane != Pane::Address || show);
+        if show && !panes.contains(&Pane::Address) {
+            panes.insert(0, Pane::Address);
+        }
+        self
+    }
+
+    /// Configures whether a second address column, holding the offset of the first byte of each row
+    /// relative to the start of the data, is shown in the HexView under construction.
+    ///
+    /// The relative addresses follow the same digit, radix and prefix settings as the address column,
+    /// and can be shown with or without it. The column is hidden by default.
+    ///
+    /// This is a shorthand for adding `Pane::RelativeAddress` to the [panes](#method.panes), right
+    /// after the address pane or to their front, unless they already contain it, or removing it
+    /// from them.
+    pub fn relative_address_column(mut self, show: bool) -> HexViewBuilder<'a> {
+        let panes = &mut self.hex_view.panes;
+        panes.retain(|&pane| pane != Pane::RelativeAddress || show);
+        if show && !panes.contains(&Pane::RelativeAddress) {
+            let position = panes.iter().position(|&pane| pane == Pane::Address).map_or(0, |address| address + 1);
+            panes.insert(position, Pane::RelativeAddress);
+        }
+        self
+    }
+
+    /// Configures the minimal number of digits of the addresses of the HexView under construction.
+    /// The default is 8.
+    ///
+    /// Addresses with more digits are written in full, see also
+    /// [auto_address_digits](#method.auto_address_digits).
+    pub fn address_digits(mut self, digits: usize) -> HexViewBuilder<'a> {
+        self.hex_view.address_digits = Some(digits);
+        self
+    }
+
+    /// Configures the HexView under construction to write its addresses with as many digits as the
+    /// largest address it shows needs.
+    ///
+    /// A [HexStream](struct.HexStream.html) does not know its length up front, so it uses the
+    /// number of digits of the largest `usize` instead.
+    pub fn auto_address_digits(mut self) -> HexViewBuilder<'a> {
+        self.hex_view.address_digits = None;
+        self
+    }
+
+    /// Configures the radix of the addresses of the HexView under construction. The default is
+    /// `Radix::Hexadecimal`.
+    pub fn address_radix(mut self, radix: Radix) -> HexViewBuilder<'a> {
+        self.hex_view.address_radix = radix;
+        self
+    }
+
+    /// Configures a prefix, such as `0x`, that is written before every address of the HexView under
+    /// construction. There is no prefix by default.
+    pub fn address_prefix(mut self, prefix: &str) -> HexViewBuilder<'a> {
+        self.hex_view.address_prefix = prefix.to_string();
+        self
+    }
+
+    /// Configures whether a ruler with the offset of every column within the row is printed above
+    /// the rows of the HexView under construction.
+    ///
+    /// The offsets are written in the radix of the hex pane, and by their last digit in the char pane.
+    pub fn column_header(mut self, show: bool) -> HexViewBuilder<'a> {
+        self.hex_view.column_header = show;
+        self
+    }
+
+    /// Configures the color of the column header of the HexView under construction.
+    pub fn column_header_color(mut self, spec: Spec) -> HexViewBuilder<'a> {
+        self.hex_view.column_header_color = Some(spec);
+        self
+    }
+
+    /// Configures the HexView under construction to split its rows with the column separator after
+    /// every `n` columns, like the extra space `hexdump -C` prints after 8 bytes. A value of 0, the
+    /// default, disables the separator.
+    ///
+    /// The separator is written in both panes. In the hex pane it is added to the space between the
+    /// groups, before the group in which the split falls.
+    pub fn column_separator_every(mut self, n: usize) -> HexViewBuilder<'a> {
+        self.hex_view.column_separator_every = n;
+        self
+    }
+
+    /// Configures the string that splits the rows of the HexView under construction, see
+    /// [column_separator_every](#method.column_separator_every). The default is a single space.
+    pub fn column_separator(mut self, separator: &str) -> HexViewBuilder<'a> {
+        self.hex_view.column_separator = separator.to_string();
+        self
+    }
+
+    /// Configures which panes the rows of the HexView under construction consist of, and in which
+    /// order. The default is the address, hex and char panes.
+    ///
+    /// See [address_column](#method.address_column) and [relative_address_column](#method.relative_address_column)
+    /// for shorthands to show or hide the address panes.
+    pub fn panes(mut self, panes: &[Pane]) -> HexViewBuilder<'a> {
+        self.hex_view.panes = panes.to_vec();
+        self
+    }
+
+    /// Configures the string written between the panes of the HexView under construction. The
+    /// default is two spaces.
+    pub fn pane_separator(mut self, separator: &str) -> HexViewBuilder<'a> {
+        self.hex_view.pane_separator = separator.to_string();
+        self
+    }
+
+    /// Configures the strings written before and after the given pane of the HexView under
+    /// construction. By default the char pane is framed by `| ` and ` |`, and the other panes have
+    /// no delimiters.
+    pub fn pane_delimiters(mut self, pane: Pane, open: &str, close: &str) -> HexViewBuilder<'a> {
+        self.hex_view.pane_delimiters.retain(|&(p, _, _)| p != pane);
+        self.hex_view.pane_delimiters.push((pane, open.to_string(), close.to_string()));
+        self
+    }
+
+    /// Configures the radix in which the bytes are written in the hex pane of the HexView under
+    /// construction. The default is `Radix::Hexadecimal`.
+    pub fn radix(mut self, radix: Radix) -> HexViewBuilder<'a> {
+        self.hex_view.radix = radix;
+        self
+    }
+
+    /// Configures whether the hexadecimal digits of the hex pane of the HexView under construction
+    /// are written in lowercase. They are written in uppercase by default.
+    pub fn lowercase_hex(mut self, lowercase: bool) -> HexViewBuilder<'a> {
+        self.hex_view.lowercase_hex = lowercase;
+        self
+    }
+
+    /// Configures a string, such as `0x` or `\x`, that is written before every byte in the hex pane
+    /// of the HexView under construction. There is no prefix by default.
+    ///
+    /// The padding of incomplete rows grows along with the prefix.
+    pub fn byte_prefix(mut self, prefix: &str) -> HexViewBuilder<'a> {
+        self.hex_view.byte_prefix = prefix.to_string();
+        self
+    }
+
+    /// Configures a string, such as `,`, that is written after every byte in the hex pane of the
+    /// HexView under construction. There is no suffix by default.
+    pub fn byte_suffix(mut self, suffix: &str) -> HexViewBuilder<'a> {
+        self.hex_view.byte_suffix = suffix.to_string();
+        self
+    }
+
+    /// Configures how the bytes are decoded into the characters of the char pane of the HexView
+    /// under construction. The default is `CharEncoding::Codepage`.
+    ///
+    /// Every byte keeps its own column in the char pane, whatever the encoding. Bytes that can not
+    /// be decoded, and control characters, are shown as the replacement character.
+    pub fn char_encoding(mut self, encoding: CharEncoding) -> HexViewBuilder<'a> {
+        self.hex_view.char_encoding = encoding;
+        self
+    }
+
+    /// Configures the character shown for the bytes that continue a multi-byte character in the char
+    /// pane of the HexView under construction. The default is `·`.
+    pub fn continuation_character(mut self, ch: char) -> HexViewBuilder<'a> {
+        self.hex_view.continuation_character = ch;
+        self
+    }
+
     /// Adds the vector of `colors` to the range color printer
     pub fn add_colors(mut self, colors: Colors) -> HexViewBuilder<'a> {
         self.hex_view.colors.extend(colors);
@@ -151,109 +642,332 @@ impl<'a> HexViewBuilder<'a> {
     }
     /// Constructs the HexView.
     pub fn finish(mut self) -> HexView<'a> {
-        self.hex_view.colors.sort_by(|&(_, ref r1), &(_, ref r2)| r1.start.cmp(&r2.start));
+        self.hex_view.color_index = ColorIndex::new(&self.hex_view.colors);
         self.hex_view
     }
 }
 
 #[derive(Default)]
-struct Padding {
-    left: usize,
-    right: usize,
+pub(crate) struct Padding {
+    pub(crate) left: usize,
+    pub(crate) right: usize,
 }
 
 impl Padding {
-    fn new(left_padding: usize, right_padding: usize) -> Padding {
+    pub(crate) fn new(left_padding: usize, right_padding: usize) -> Padding {
         Padding {
             left: left_padding,
             right: right_padding,
         }
     }
+}
 
-    fn from_left(left_padding: usize) -> Padding {
-        Padding {
-            left: left_padding,
-            right: 0,
+fn fmt_bytes_as_hex<W: WriteColor>(f: &mut W, view: &HexView, bytes: &[u8], color_range: &ColorRange, padding: &Padding) -> io::Result<()> {
+    let mut separator = "";
+
+    for group in groups(padding.left + bytes.len() + padding.right, view.group_size) {
+        f.write_all(separator.as_bytes())?;
+        f.write_all(column_separator(view, group.clone()).as_bytes())?;
+
+        for n in 0..group.len() {
+            let column = group_column(&group, n, view.byte_order);
+            if column < padding.left || column >= padding.left + bytes.len() {
+                write!(f, "{:1$}", "", view.byte_width())?;
+                continue;
+            }
+
+            let i = column - padding.left;
+            match color_range.get(i) {
+                Some(rgb) => {
+                    f.set_color(rgb)?;
+                    view.fmt_byte(f, bytes[i])?;
+                    f.reset()?;
+                },
+                None => view.fmt_byte(f, bytes[i])?,
+            }
         }
+
+        separator = " ";
     }
 
-    fn from_right(right_padding: usize) -> Padding {
-        Padding {
-            left: 0,
-            right: right_padding,
-        }
+    Ok(())
+}
+
+/// Returns the column separator if the row is split within or right before the given `columns`,
+/// or an empty string otherwise.
+pub(crate) fn column_separator<'v>(view: &'v HexView, columns: Range<usize>) -> &'v str {
+    let n = view.column_separator_every;
+    if n != 0 && columns.start != 0 && columns.into_iter().any(|column| column % n == 0) {
+        &view.column_separator
+    } else {
+        ""
     }
 }
 
-fn fmt_bytes_as_hex<W: WriteColor>(f: &mut W, bytes: &[u8], color_range: &ColorRange, padding: &Padding) -> io::Result<()> {
-    let mut separator = "";
+/// Returns the columns of the row that are shown in the given column of the char pane.
+pub(crate) fn char_column_bytes(view: &HexView, column: usize) -> Range<usize> {
+    let bytes_per_column = view.char_encoding.bytes_per_column();
+    column * bytes_per_column..(column + 1) * bytes_per_column
+}
 
-    for _ in 0..padding.left {
-        write!(f, "{}  ", separator)?;
-        separator = " ";
+/// Splits a row of `row_width` columns into the groups of the hex pane.
+pub(crate) fn groups(row_width: usize, group_size: usize) -> impl Iterator<Item = Range<usize>> {
+    (0..(row_width + group_size - 1) / group_size).map(move |group| group * group_size..cmp::min((group + 1) * group_size, row_width))
+}
+
+/// Returns the column that is displayed at position `n` of the given group.
+pub(crate) fn group_column(group: &Range<usize>, n: usize, byte_order: Endian) -> usize {
+    match byte_order {
+        Endian::Big => group.start + n,
+        Endian::Little => group.end - 1 - n,
+    }
+}
+
+/// Returns the character that starts at each of the bytes in `range` of `data`. The bytes around
+/// the range are only used to decode the characters that cross its boundaries.
+///
+/// The index of `data` is assumed to be aligned to the code units of the encoding.
+pub(crate) fn row_chars(view: &HexView, data: &[u8], range: Range<usize>) -> Vec<Option<char>> {
+    let repl_char = view.replacement_character;
+    match view.char_encoding {
+        CharEncoding::Codepage => data[range].iter()
+            .map(|&byte| Some(byte_mapping::as_char(byte, view.codepage, repl_char)))
+            .collect(),
+        CharEncoding::Utf8 => range
+            .map(|i| Some(byte_mapping::utf8_char_at(data, i, repl_char, view.continuation_character)))
+            .collect(),
+        CharEncoding::Utf16(byte_order) => range
+            .map(|i| if i % 2 == 0 { Some(byte_mapping::utf16_char_at(data, i, byte_order, repl_char, view.continuation_character)) } else { None })
+            .collect(),
     }
+}
+
+/// Returns the columns of the char pane of the row showing the bytes in `range` of `data`. A column
+/// holds the character shown in it together with the index in the row of the byte it starts at,
+/// or `None` if it is blank.
+///
+/// Every row has the same number of columns, whatever its padding.
+pub(crate) fn char_columns(view: &HexView, data: &[u8], range: Range<usize>, padding: &Padding) -> Vec<Option<(usize, char)>> {
+    let chars = row_chars(view, data, range);
+    let bytes_per_column = view.char_encoding.bytes_per_column();
+    let row_width = padding.left + chars.len() + padding.right;
+
+    (0..(row_width + bytes_per_column - 1) / bytes_per_column)
+        .map(|column| (column * bytes_per_column..cmp::min((column + 1) * bytes_per_column, row_width))
+            .filter(|&c| padding.left <= c && c < padding.left + chars.len())
+            .find_map(|c| chars[c - padding.left].map(|ch| (c - padding.left, ch))))
+        .collect()
+}
 
-    for (i, byte) in bytes.iter().enumerate() {
-        match color_range.get(i) {
-            Some(rgb) => {
-                write!(f, "{}", separator)?;
-                color!(f, rgb, format!("{:02X}", byte))?;
+fn fmt_bytes_as_char<W: WriteColor>(f: &mut W, view: &HexView, columns: &[Option<(usize, char)>], color_range: &ColorRange) -> io::Result<()> {
+    for (n, column) in columns.iter().enumerate() {
+        f.write_all(column_separator(view, char_column_bytes(view, n)).as_bytes())?;
+        match *column {
+            Some((i, ch)) => {
+                let mut encoded = [0; 4];
+                match color_range.get(i) {
+                    Some(rgb) => {
+                        f.set_color(rgb)?;
+                        f.write_all(ch.encode_utf8(&mut encoded).as_bytes())?;
+                        f.reset()?;
+                    },
+                    None => f.write_all(ch.encode_utf8(&mut encoded).as_bytes())?,
+                }
             },
-            None => write!(f, "{}{:02X}", separator, byte)?,
+            None => f.write_all(b" ")?,
         }
-        separator = " ";
     }
 
-    for _ in 0..padding.right {
-        write!(f, "{}  ", separator)?;
-        separator = " ";
+    Ok(())
+}
+
+/// Writes the row showing the bytes in `range` of `data`, at `address` and at `relative_address`
+/// within the data shown. Only the bytes in `range` are shown, the bytes around it are used to
+/// decode the characters that cross the boundaries of the row.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn fmt_line<W: WriteColor>(f: &mut W, view: &HexView, address: usize, relative_address: usize, data: &[u8], range: Range<usize>, color_range: &mut ColorRange, padding: &Padding) -> io::Result<()> {
+    let bytes = &data[range.clone()];
+    let mut separator = "";
+    for &pane in view.panes() {
+        let (open, close) = view.pane_delimiters(pane);
+        write!(f, "{}{}", separator, open)?;
+        match pane {
+            Pane::Address => write!(f, "{}", view.fmt_address(address))?,
+            Pane::RelativeAddress => write!(f, "{}", view.fmt_address(relative_address))?,
+            Pane::Hex => fmt_bytes_as_hex(f, view, bytes, color_range, padding)?,
+            Pane::Char => fmt_bytes_as_char(f, view, &char_columns(view, data, range.clone(), padding), color_range)?,
+        }
+        write!(f, "{}", close)?;
+        separator = &view.pane_separator;
+    }
+
+    if view.annotation_margin {
+        let mut separator = "  ";
+        for annotation in annotations_starting_in(view, color_range.offset(), bytes.len()) {
+            write!(f, "{}", separator)?;
+            let spec = &annotation.spec;
+            color!(f, spec, annotation.label)?;
+            separator = ", ";
+        }
     }
 
     Ok(())
 }
 
-fn fmt_bytes_as_char<W: WriteColor>(f: &mut W, cp: &[char], repl_char: char, bytes: &[u8], color_range: &ColorRange, padding: &Padding) -> io::Result<()> {
-    for _ in 0..padding.left {
-        write!(f, " ")?;
+/// Returns the annotations whose range starts in the `len` offsets from `offset` on. The offsets
+/// wrap around like the offsets of a [ColorRange](../color/struct.ColorRange.html).
+fn annotations_starting_in<'a>(view: &'a HexView, offset: usize, len: usize) -> impl Iterator<Item = &'a Annotation> + 'a {
+    view.annotations.iter().filter(move |annotation| annotation.range.start.wrapping_sub(offset) < len)
+}
+
+/// Writes the column header, if it is enabled, followed by a line break.
+pub(crate) fn fmt_header<W: WriteColor>(f: &mut W, view: &HexView) -> io::Result<()> {
+    if !view.column_header {
+        return Ok(());
     }
 
-    for (i, &byte) in bytes.iter().enumerate() {
-        let byte = byte_mapping::as_char(byte, cp, repl_char);
-        match color_range.get(i) {
-            Some(rgb) => {
-                color!(f, rgb, format!("{}", byte))?;
+    let mut header = String::new();
+    let mut separator = "";
+    for &pane in view.panes() {
+        header += separator;
+        separator = &view.pane_separator;
+
+        let (open, close) = view.pane_delimiters(pane);
+        match pane {
+            Pane::Address | Pane::RelativeAddress => {
+                let width = open.chars().count() + view.fmt_address(view.address_offset).chars().count() + close.chars().count();
+                header += &format!("{:1$}", "", width);
+                continue;
+            },
+            Pane::Hex => {
+                header += open;
+                let mut group_separator = "";
+                for group in groups(view.row_width, view.group_size) {
+                    header += group_separator;
+                    header += column_separator(view, group.clone());
+                    for n in 0..group.len() {
+                        let label = column_label(view, group_column(&group, n, view.byte_order), view.radix.digits());
+                        header += &format!("{:>2$}{:3$}", label, "", view.byte_prefix.chars().count() + label.len(), view.byte_suffix.chars().count());
+                    }
+                    group_separator = " ";
+                }
+            },
+            Pane::Char => {
+                header += open;
+                let bytes_per_column = view.char_encoding.bytes_per_column();
+                for column in 0..(view.row_width + bytes_per_column - 1) / bytes_per_column {
+                    let bytes = char_column_bytes(view, column);
+                    header += column_separator(view, bytes.clone());
+                    header += &column_label(view, bytes.start, 1);
+                }
             },
-            _ => write!(f, "{}", byte)?,
         }
+        header += close;
+    }
+
+    match view.column_header_color {
+        Some(ref spec) => color!(f, spec, header)?,
+        None => write!(f, "{}", header)?,
     }
+    writeln!(f)
+}
+
+/// Writes the last `digits` digits of the offset of a column.
+fn column_label(view: &HexView, column: usize, digits: usize) -> String {
+    let label = view.radix.fmt_number(column, digits);
+    view.fmt_digits(label[label.len() - digits..].to_string())
+}
 
-    for _ in 0..padding.right {
-        write!(f, " ")?;
+/// Writes the legend of the annotations, if there are any, below the hextable.
+pub(crate) fn fmt_legend<W: WriteColor>(f: &mut W, view: &HexView) -> io::Result<()> {
+    if view.annotations.is_empty() {
+        return Ok(());
     }
 
+    let label_width = view.annotations.iter().map(|annotation| annotation.label.chars().count()).max().unwrap_or(0);
+    write!(f, "\n\nLegend:")?;
+    for annotation in &view.annotations {
+        let spec = &annotation.spec;
+        write!(f, "\n  ")?;
+        color!(f, spec, format!("{:1$}", annotation.label, label_width))?;
+        write!(f, "  {}..{}", view.fmt_address(view.address_offset + annotation.range.start), view.fmt_address(view.address_offset + annotation.range.end))?;
+    }
     Ok(())
 }
 
-fn fmt_line<W: WriteColor>(f: &mut W, address: usize, cp: &[char], repl_char: char, bytes: &[u8], color_range: &mut ColorRange, padding: &Padding) -> io::Result<()> {
-    write!(f, "{:0width$X}", address, width = 8)?;
+/// Returns the range of `data` shown in the given row, together with the row's padding, or `None`
+/// if the row does not contain any of the data.
+pub(crate) fn row_at(data: &[u8], begin_padding: usize, row_width: usize, row: usize) -> Option<(Range<usize>, Padding)> {
+    let begin = (row * row_width).saturating_sub(begin_padding);
+    let end = cmp::min(data.len(), (row + 1) * row_width - begin_padding);
+    if begin >= end {
+        return if data.is_empty() && row == 0 { Some((0..0, Padding::new(begin_padding, row_width - begin_padding))) } else { None };
+    }
 
-    write!(f, "  ")?;
-    fmt_bytes_as_hex(f, bytes, &color_range, &padding)?;
-    write!(f, "  ")?;
+    let left = if row == 0 { begin_padding } else { 0 };
+    Some((begin..end, Padding::new(left, row_width - left - (end - begin))))
+}
 
-    write!(f, "| ")?;
-    fmt_bytes_as_char(f, cp, repl_char, bytes, &color_range, &padding)?;
-    write!(f, " |")?;
+/// How a row is written when repeated rows are squeezed.
+pub(crate) enum Squeeze {
+    /// The row is written.
+    Row,
+    /// The row starts a run of squeezed rows, which is written as a single `*` line.
+    Start,
+    /// The row continues a run of squeezed rows and is not written.
+    Continue,
+}
 
-    Ok(())
+/// Tracks the rows of a hextable in order, to decide which of them are squeezed.
+#[derive(Default)]
+pub(crate) struct Squeezer {
+    previous_row: Option<Vec<u8>>,
+    squeezing: bool,
+}
+
+impl Squeezer {
+    /// Returns how the row showing `bytes` with `padding` is written, given the `color_range` at
+    /// the start of the row. A full row is squeezed when it repeats the row before it with the same
+    /// colors, unless it is the last row or an annotation starts in it.
+    pub(crate) fn squeeze(&mut self, view: &HexView, bytes: &[u8], padding: &Padding, is_last_row: bool, color_range: &ColorRange) -> Squeeze {
+        if !view.squeeze_repeats {
+            return Squeeze::Row;
+        }
+        let is_full_row = padding.left == 0 && padding.right == 0;
+        let is_repeat = is_full_row && !is_last_row
+            && self.previous_row.as_deref() == Some(bytes)
+            && has_same_colors_as_previous_row(color_range, view.row_width)
+            && annotations_starting_in(view, color_range.offset(), bytes.len()).next().is_none();
+
+        let squeeze = match (is_repeat, self.squeezing) {
+            (false, _) => Squeeze::Row,
+            (true, false) => Squeeze::Start,
+            (true, true) => Squeeze::Continue,
+        };
+        self.squeezing = is_repeat;
+        if is_full_row {
+            let previous_row = self.previous_row.get_or_insert_with(Vec::new);
+            previous_row.clear();
+            previous_row.extend_from_slice(bytes);
+        } else {
+            self.previous_row = None;
+        }
+        squeeze
+    }
+}
+
+fn has_same_colors_as_previous_row(color_range: &ColorRange, row_width: usize) -> bool {
+    let mut previous_color_range = color_range.clone();
+    previous_color_range.update_offset(color_range.offset().wrapping_sub(row_width));
+    (0..row_width).all(|i| previous_color_range.get(i) == color_range.get(i))
 }
 
-fn calculate_begin_padding(address_offset: usize, row_width: usize) -> usize {
+pub(crate) fn calculate_begin_padding(address_offset: usize, row_width: usize) -> usize {
     debug_assert!(row_width != 0, "A zero row width is can not be used to calculate the begin padding");
     address_offset % row_width
 }
 
-fn calculate_end_padding(data_size: usize, row_width: usize) -> usize {
+pub(crate) fn calculate_end_padding(data_size: usize, row_width: usize) -> usize {
     debug_assert!(row_width != 0, "A zero row width is can not be used to calculate the end padding");
     (row_width - data_size % row_width) % row_width
 }
@@ -275,13 +989,14 @@ impl<'a> std::fmt::Display for HexView<'a> {
 }
 
 #[cfg(test)]
+#[allow(clippy::char_lit_as_u8)]
 mod tests {
     use super::*;
     use std;
+    use color;
 
     #[test]
     fn test_begin_padding() {
-        // Rust 1.13 needs the fully qualified name here
         assert_eq!(super::calculate_begin_padding(0, 16), 0);
         assert_eq!(super::calculate_begin_padding(16, 16), 0);
         assert_eq!(super::calculate_begin_padding(54, 16), 6);
@@ -343,6 +1058,607 @@ mod tests {
         assert_eq!(result, "00000000                 61 61 61 61 61 61 61 61           |      aaaaaaaa    |");
     }
 
+    #[test]
+    fn bytes_can_be_grouped() {
+        let data: Vec<u8> = (0x40..0x50).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .group_size(4)
+            .finish();
+
+        let result = format!("{}", row_view);
+
+        assert_eq!(result, "00000000  40414243 44454647 48494A4B 4C4D4E4F  | @ABCDEFGHIJKLMNO |");
+    }
+
+    #[test]
+    #[should_panic]
+    fn a_group_size_of_zero_is_rejected() {
+        HexViewBuilder::new(&[]).group_size(0);
+    }
+
+    #[test]
+    fn grouped_bytes_can_be_displayed_as_little_endian() {
+        let data: Vec<u8> = (0x61..0x69).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(8)
+            .group_size(4)
+            .byte_order(Endian::Little)
+            .finish();
+
+        let result = format!("{}", row_view);
+
+        assert_eq!(result, "00000000  64636261 68676665  | abcdefgh |");
+    }
+
+    #[test]
+    fn padding_is_reversed_along_with_little_endian_groups() {
+        let data: Vec<u8> = (0x41..0x46).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(2)
+            .row_width(8)
+            .group_size(4)
+            .byte_order(Endian::Little)
+            .finish();
+
+        let result = format!("{}", row_view);
+
+        assert_eq!(result, "00000000  4241       454443  |   ABCDE  |");
+    }
+
+    #[test]
+    fn repeated_rows_can_be_squeezed() {
+        let mut data = vec![0x41; 4];
+        data.extend_from_slice(&[0xFF; 16]);
+        data.extend_from_slice(&[0x42; 3]);
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .squeeze_repeats(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("00000000  41 41 41 41  | AAAA |"));
+        assert_eq!(lines.next(), Some("00000004  FF FF FF FF  | .... |"));
+        assert_eq!(lines.next(), Some("*"));
+        assert_eq!(lines.next(), Some("00000014  42 42 42     | BBB  |"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn squeezing_stops_at_rows_with_different_colors() {
+        let data = [0xFF; 22];
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(2)
+            .row_width(4)
+            .squeeze_repeats(true)
+            .add_colors(vec![(color::red(), 13..14)])
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("00000000        FF FF  |   .. |"));
+        assert_eq!(lines.next(), Some("00000004  FF FF FF FF  | .... |"));
+        assert_eq!(lines.next(), Some("*"));
+        assert_eq!(lines.next(), Some("0000000C  FF FF FF FF  | .... |"));
+        assert_eq!(lines.next(), Some("00000010  FF FF FF FF  | .... |"));
+        assert_eq!(lines.next(), Some("00000014  FF FF FF FF  | .... |"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn the_row_ending_the_data_or_window_is_never_squeezed() {
+        let data = [0xFF; 16];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .squeeze_repeats(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut window = ColorlessString(String::new());
+        row_view.render_rows(0..3, &mut window).unwrap();
+
+        assert_eq!(result, "00000000  FF FF FF FF  | .... |\n*\n0000000C  FF FF FF FF  | .... |");
+        assert_eq!(window.0, "00000000  FF FF FF FF  | .... |\n*\n00000008  FF FF FF FF  | .... |");
+    }
+
+    #[test]
+    fn annotations_are_listed_in_a_legend() {
+        let data: Vec<u8> = (0x41..0x49).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(0x20)
+            .row_width(4)
+            .annotate(0..2, "header.len", color::red())
+            .annotate(2..7, "payload", color::blue())
+            .finish();
+
+        let result = format!("{}", row_view);
+
+        assert_eq!(result, "00000020  41 42 43 44  | ABCD |\n\
+                            00000024  45 46 47 48  | EFGH |\n\
+                            \n\
+                            Legend:\n\
+                            \x20 header.len  00000020..00000022\n\
+                            \x20 payload     00000022..00000027");
+    }
+
+    #[test]
+    fn annotations_can_be_shown_in_the_margin() {
+        let data: Vec<u8> = (0x41..0x49).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .annotate(0..2, "header.len", color::red())
+            .annotate(2..3, "header.type", color::green())
+            .annotate(5..8, "payload", color::blue())
+            .annotation_margin(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("00000000  41 42 43 44  | ABCD |  header.len, header.type"));
+        assert_eq!(lines.next(), Some("00000004  45 46 47 48  | EFGH |  payload"));
+    }
+
+    #[test]
+    fn rows_starting_an_annotation_are_not_squeezed() {
+        let data = [0u8; 64];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .squeeze_repeats(true)
+            .annotate(0..16, "outer", color::red())
+            .annotate(8..12, "inner", color::red())
+            .annotation_margin(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("00000000  00 00 00 00  | .... |  outer"));
+        assert_eq!(lines.next(), Some("*"));
+        assert_eq!(lines.next(), Some("00000008  00 00 00 00  | .... |  inner"));
+        assert_eq!(lines.next(), Some("*"));
+    }
+
+    #[test]
+    fn utf8_characters_are_shown_on_their_lead_byte() {
+        let data = "aé€𝐀".as_bytes();
+
+        let row_view = HexViewBuilder::new(data)
+            .row_width(10)
+            .char_encoding(CharEncoding::Utf8)
+            .finish();
+
+        let result = format!("{}", row_view);
+
+        assert_eq!(result, "00000000  61 C3 A9 E2 82 AC F0 9D 90 80  | aé·€··𝐀··· |");
+    }
+
+    #[test]
+    fn characters_not_taking_a_single_column_keep_the_frame_aligned() {
+        let data = "abcdefgh世界😀e\u{301}".as_bytes();
+
+        let row_view = HexViewBuilder::new(data)
+            .row_width(8)
+            .char_encoding(CharEncoding::Utf8)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("00000000  61 62 63 64 65 66 67 68  | abcdefgh |"));
+        assert_eq!(lines.next(), Some("00000008  E4 B8 96 E7 95 8C F0 9F  | .··.··.· |"));
+        assert_eq!(lines.next(), Some("00000010  98 80 65 CC 81           | ··e.·    |"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn invalid_utf8_is_shown_as_the_replacement_character() {
+        let data = [0x41, 0x80, 0xC3, 0x41, 0xE2, 0x82, 0x0A, 0xFF];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(8)
+            .char_encoding(CharEncoding::Utf8)
+            .finish();
+
+        let result = format!("{}", row_view);
+
+        assert_eq!(result, "00000000  41 80 C3 41 E2 82 0A FF  | A..A.... |");
+    }
+
+    #[test]
+    fn utf8_characters_can_cross_the_row_boundary() {
+        let data = "abcé€".as_bytes();
+
+        let row_view = HexViewBuilder::new(data)
+            .row_width(4)
+            .char_encoding(CharEncoding::Utf8)
+            .continuation_character('~')
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("00000000  61 62 63 C3  | abcé |"));
+        assert_eq!(lines.next(), Some("00000004  A9 E2 82 AC  | ~€~~ |"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn utf16_code_units_take_a_column_each() {
+        let data = [0x48, 0x00, 0x69, 0x00, 0x35, 0xD8, 0x00, 0xDC];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(8)
+            .char_encoding(CharEncoding::Utf16(Endian::Little))
+            .finish();
+
+        let result = format!("{}", row_view);
+
+        assert_eq!(result, "00000000  48 00 69 00 35 D8 00 DC  | Hi𝐀· |");
+    }
+
+    #[test]
+    fn unpaired_utf16_surrogates_are_shown_as_the_replacement_character() {
+        let data = [0xD8, 0x00, 0x00, 0x41, 0xDC, 0x00, 0x00, 0x0A, 0x00];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(9)
+            .char_encoding(CharEncoding::Utf16(Endian::Big))
+            .finish();
+
+        let result = format!("{}", row_view);
+
+        assert_eq!(result, "00000000  D8 00 00 41 DC 00 00 0A 00  | .A... |");
+    }
+
+    #[test]
+    fn the_utf16_char_pane_keeps_its_width_on_padded_rows() {
+        let data = [0x41, 0x00, 0x42, 0x00, 0x43, 0x00, 0x44, 0x00];
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(3)
+            .row_width(4)
+            .char_encoding(CharEncoding::Utf16(Endian::Little))
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("00000000           41  |  A |"));
+        assert_eq!(lines.next(), Some("00000004  00 42 00 43  | BC |"));
+        assert_eq!(lines.next(), Some("00000008  00 44 00     | D  |"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn bytes_can_be_written_in_octal_and_decimal() {
+        let data = [0x00, 0x2A, 0x41, 0xFF];
+
+        let octal = HexViewBuilder::new(&data).row_width(4).radix(Radix::Octal).finish();
+        let decimal = HexViewBuilder::new(&data).row_width(4).radix(Radix::Decimal).finish();
+
+        assert_eq!(format!("{}", octal), "00000000  000 052 101 377  | .*A. |");
+        assert_eq!(format!("{}", decimal), "00000000  000 042 065 255  | .*A. |");
+    }
+
+    #[test]
+    fn the_padding_has_the_width_of_the_radix() {
+        let data = [0x30, 0xA5, 0x41];
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(1)
+            .row_width(2)
+            .radix(Radix::Binary)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("00000000           00110000  |  0 |"));
+        assert_eq!(lines.next(), Some("00000002  10100101 01000001  | ÑA |"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn the_address_digits_can_be_derived_from_the_largest_address() {
+        let data = [0x41; 20];
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(0x1_0000 - 16)
+            .auto_address_digits()
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("0FFF0  41 41 41 41 41 41 41 41 41 41 41 41 41 41 41 41  | AAAAAAAAAAAAAAAA |"));
+        assert_eq!(lines.next(), Some("10000  41 41 41 41                                      | AAAA             |"));
+    }
+
+    #[test]
+    fn addresses_can_be_decimal_with_a_prefix() {
+        let data = [0x41; 4];
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(20)
+            .row_width(4)
+            .address_digits(4)
+            .address_radix(Radix::Decimal)
+            .address_prefix("#")
+            .finish();
+
+        assert_eq!(format!("{}", row_view), "#0020  41 41 41 41  | AAAA |");
+    }
+
+    #[test]
+    fn the_address_column_can_be_hidden() {
+        let data = [0x41; 4];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .address_column(false)
+            .finish();
+
+        assert_eq!(format!("{}", row_view), "41 41 41 41  | AAAA |");
+    }
+
+    #[test]
+    fn relative_addresses_can_be_shown_next_to_the_absolute_ones() {
+        let data = [0x41; 6];
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(0x1F3A2)
+            .row_width(4)
+            .address_digits(5)
+            .relative_address_column(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("1F3A0  00000        41 41  |   AA |"));
+        assert_eq!(lines.next(), Some("1F3A4  00002  41 41 41 41  | AAAA |"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn the_relative_address_column_can_be_shown_on_its_own() {
+        let data = [0x41; 6];
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(0x1F3A0)
+            .row_width(4)
+            .address_column(false)
+            .relative_address_column(true)
+            .auto_address_digits()
+            .address_radix(Radix::Decimal)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("000000  41 41 41 41  | AAAA |"));
+        assert_eq!(lines.next(), Some("000004  41 41        | AA   |"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn rendered_rows_are_the_rows_of_the_full_view() {
+        let data = "Größe über alles, schön öffentlich und ärgerlich".repeat(3).into_bytes();
+
+        let view = HexViewBuilder::new(&data)
+            .address_offset(0x1003)
+            .row_width(8)
+            .char_encoding(CharEncoding::Utf8)
+            .relative_address_column(true)
+            .add_colors(vec![(color::red(), 17..43)])
+            .finish();
+
+        let full = format!("{}", view);
+        let mut window = ColorlessString(String::new());
+        view.render_rows(3..7, &mut window).unwrap();
+
+        assert_eq!(window.0, full.lines().skip(3).take(4).collect::<Vec<_>>().join("\n"));
+    }
+
+    #[test]
+    fn rendered_bytes_keep_the_alignment_of_their_rows() {
+        let data: Vec<u8> = (0x41..0x51).collect();
+
+        let view = HexViewBuilder::new(&data)
+            .address_offset(0x100)
+            .row_width(4)
+            .finish();
+
+        let mut window = ColorlessString(String::new());
+        view.render_bytes(5..10, &mut window).unwrap();
+        let mut lines = window.0.lines();
+
+        assert_eq!(lines.next(), Some("00000104     46 47 48  |  FGH |"));
+        assert_eq!(lines.next(), Some("00000108  49 4A        | IJ   |"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn windows_past_the_end_of_the_data_are_empty() {
+        let data = [0x41; 8];
+
+        let view = HexViewBuilder::new(&data).row_width(4).finish();
+
+        let mut window = ColorlessString(String::new());
+        view.render_rows(1..10, &mut window).unwrap();
+        view.render_rows(2..10, &mut window).unwrap();
+        view.render_bytes(8..10, &mut window).unwrap();
+
+        assert_eq!(window.0, "00000004  41 41 41 41  | AAAA |");
+    }
+
+    #[test]
+    fn a_column_header_can_be_printed_above_the_rows() {
+        let data: Vec<u8> = (0x41..0x51).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .column_header(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("          00 01 02 03 04 05 06 07 08 09 0A 0B 0C 0D 0E 0F  | 0123456789ABCDEF |"));
+        assert_eq!(lines.next(), Some("00000000  41 42 43 44 45 46 47 48 49 4A 4B 4C 4D 4E 4F 50  | ABCDEFGHIJKLMNOP |"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn the_column_header_follows_the_layout_of_the_rows() {
+        let data = [0x41; 12];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(12)
+            .group_size(4)
+            .byte_order(Endian::Little)
+            .radix(Radix::Octal)
+            .auto_address_digits()
+            .column_header(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("   003002001000 007006005004 013012011010  | 012345670123 |"));
+        assert_eq!(lines.next(), Some("0  101101101101 101101101101 101101101101  | AAAAAAAAAAAA |"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn rows_can_be_split_by_a_column_separator() {
+        let data: Vec<u8> = (0x41..0x55).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(2)
+            .column_separator_every(8)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("00000000        41 42 43 44 45 46  47 48 49 4A 4B 4C 4D 4E  |   ABCDEF GHIJKLMN |"));
+        assert_eq!(lines.next(), Some("00000010  4F 50 51 52 53 54                                 | OPQRST            |"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn the_column_separator_can_be_configured() {
+        let data: Vec<u8> = (0x41..0x47).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(6)
+            .group_size(2)
+            .column_separator_every(3)
+            .column_separator("| ")
+            .column_header(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("          0001 | 0203 0405  | 012| 345 |"));
+        assert_eq!(lines.next(), Some("00000000  4142 | 4344 4546  | ABC| DEF |"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn the_hex_pane_can_be_shown_on_its_own() {
+        let data = [0x41, 0x42, 0x43];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .panes(&[Pane::Hex])
+            .finish();
+
+        assert_eq!(format!("{}", row_view), "41 42 43   ");
+    }
+
+    #[test]
+    fn the_panes_can_be_reordered_and_delimited() {
+        let data = [0x41, 0x42, 0x43, 0x44];
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(0x10)
+            .row_width(4)
+            .panes(&[Pane::Char, Pane::Address, Pane::Hex])
+            .pane_separator(" ")
+            .pane_delimiters(Pane::Char, "\"", "\"")
+            .pane_delimiters(Pane::Address, "@", ":")
+            .column_header(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("\"0123\"            00 01 02 03"));
+        assert_eq!(lines.next(), Some("\"ABCD\" @00000010: 41 42 43 44"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn the_address_columns_are_shorthands_for_editing_the_panes() {
+        let data = [0x41, 0x42];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(2)
+            .panes(&[Pane::Char, Pane::Address])
+            .relative_address_column(true)
+            .address_column(false)
+            .finish();
+
+        assert_eq!(format!("{}", row_view), "| AB |  00000000");
+    }
+
+    #[test]
+    fn hex_digits_can_be_lowercase() {
+        let data = [0xAB, 0xCD];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(2)
+            .lowercase_hex(true)
+            .finish();
+
+        assert_eq!(format!("{}", row_view), "00000000  ab cd  | ½═ |");
+    }
+
+    #[test]
+    fn the_padding_follows_the_byte_prefix_and_suffix() {
+        let data = [0x41, 0x42, 0x43];
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(1)
+            .row_width(2)
+            .byte_prefix("\\x")
+            .byte_suffix(",")
+            .column_header(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("            00    01   | 01 |"));
+        assert_eq!(lines.next(), Some("00000000        \\x41,  |  A |"));
+        assert_eq!(lines.next(), Some("00000002  \\x42, \\x43,  | BC |"));
+        assert_eq!(lines.next(), None);
+    }
+
     #[test]
     fn decreasing_the_row_width_increases_the_total_character_count() {
         let data: Vec<u8> = (0..64).collect();
@@ -0,0 +1,344 @@
+use std::cmp;
+use std::io::{self, Write};
+
+use color::{Color, Spec};
+use format::{self, HexView, Pane, Squeeze, Squeezer};
+
+const SCRIPT: &str = "<script>
+(function () {
+    var table = document.currentScript.previousElementSibling;
+    function highlight(event, on) {
+        var offset = event.target.getAttribute && event.target.getAttribute(\"data-offset\");
+        if (offset === null || offset === undefined) {
+            return;
+        }
+        var twins = table.querySelectorAll(\"[data-offset='\" + offset + \"']\");
+        for (var i = 0; i < twins.length; i++) {
+            twins[i].classList.toggle(\"hexplay-hover\", on);
+        }
+    }
+    table.addEventListener(\"mouseover\", function (event) { highlight(event, true); });
+    table.addEventListener(\"mouseout\", function (event) { highlight(event, false); });
+})();
+</script>
+";
+
+impl<'a> HexView<'a> {
+    /// Renders the hextable as an HTML fragment, see [fmt_html](#method.fmt_html).
+    pub fn to_html(&self) -> io::Result<String> {
+        let mut html = Vec::new();
+        self.fmt_html(&mut html, "hexplay")?;
+        Ok(String::from_utf8(html).expect("the html is written as utf-8"))
+    }
+
+    /// Writes the hextable as an HTML fragment to `writer`.
+    ///
+    /// The fragment consists of a `<style>` block, a `<table>` with the given `id` and a `<script>`
+    /// that highlights both representations of a byte when hovering either of them. Every byte is
+    /// a `<span>` whose `data-offset` attribute holds its address, and every color range becomes a
+    /// CSS class named `hexplay-color-N`, where `N` is the position of the range in the order the
+    /// ranges were added. The style rules are written from the widest range to the narrowest, so
+    /// overlapping ranges are layered like in the terminal.
+    ///
+    /// The `id` scopes the generated style rules, so several dumps can be embedded in the same page.
+    /// It has to start with an ASCII letter, followed by ASCII letters, digits, `-` and `_`.
+    ///
+    /// An invalid `id` or a row width of 0 results in an `InvalidInput` error.
+    pub fn fmt_html<W: Write>(&self, writer: &mut W, id: &str) -> io::Result<()> {
+        if self.row_width == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Invalid HexView::width"));
+        }
+        if !is_valid_id(id) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Invalid HTML id"));
+        }
+
+        writeln!(writer, "<style>")?;
+        writeln!(writer, "#{0} {{ font-family: monospace; border-collapse: collapse; }}", id)?;
+        writeln!(writer, "#{0} td {{ padding: 0 1ch; white-space: pre; }}", id)?;
+        writeln!(writer, "#{0} .hexplay-hover {{ outline: 1px solid; }}", id)?;
+        let mut layers: Vec<usize> = (0..self.colors.len()).collect();
+        layers.sort_by_key(|&i| cmp::Reverse(self.colors[i].1.len()));
+        for i in layers {
+            writeln!(writer, "#{} .hexplay-color-{} {{{} }}", id, i, css_declarations(&self.colors[i].0))?;
+        }
+        writeln!(writer, "</style>")?;
+
+        writeln!(writer, "<table id=\"{}\" class=\"hexplay\">", id)?;
+        self.fmt_html_rows(writer)?;
+        writeln!(writer, "</table>")?;
+
+        write!(writer, "{}", SCRIPT)
+    }
+
+    fn fmt_html_rows<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let begin_padding = format::calculate_begin_padding(self.address_offset, self.row_width);
+        let (byte_prefix, byte_suffix) = (escape(&self.byte_prefix), escape(&self.byte_suffix));
+        let mut squeezer = Squeezer::default();
+        let mut color_range = self.color_range();
+
+        let mut row = 0;
+        while let Some((range, padding)) = format::row_at(self.data, begin_padding, self.row_width, row) {
+            let address = self.address_offset - begin_padding + row * self.row_width;
+            let slice = &self.data[range.clone()];
+            color_range.update_offset(range.start);
+            row += 1;
+
+            match squeezer.squeeze(self, slice, &padding, range.end == self.data.len(), &color_range) {
+                Squeeze::Row => {},
+                Squeeze::Start => {
+                    writeln!(writer, "<tr><td colspan=\"{}\">*</td></tr>", self.panes().len())?;
+                    continue;
+                },
+                Squeeze::Continue => continue,
+            }
+
+            let classes = |i: usize| -> String {
+                color_range.layers(i).iter().map(|n| format!(" hexplay-color-{}", n)).collect()
+            };
+
+            write!(writer, "<tr>")?;
+            for &pane in self.panes() {
+                let (open, close) = self.pane_delimiters(pane);
+                match pane {
+                    Pane::Address => write!(writer, "<td class=\"hexplay-address\">{}{}", escape(open), escape(&self.fmt_address(address)))?,
+                    Pane::RelativeAddress => write!(writer, "<td class=\"hexplay-relative-address\">{}{}", escape(open), escape(&self.fmt_address(range.start)))?,
+                    Pane::Hex => {
+                        write!(writer, "<td class=\"hexplay-hex\">{}", escape(open))?;
+                        let mut separator = "";
+                        for group in format::groups(self.row_width, self.group_size) {
+                            write!(writer, "{}{}", separator, escape(format::column_separator(self, group.clone())))?;
+                            for n in 0..group.len() {
+                                let column = format::group_column(&group, n, self.byte_order);
+                                if column < padding.left || column >= padding.left + slice.len() {
+                                    write!(writer, "{:1$}", "", self.byte_width())?;
+                                } else {
+                                    let i = column - padding.left;
+                                    write!(writer, "<span class=\"hexplay-byte{}\" data-offset=\"{}\">{}", classes(i), address + column, byte_prefix)?;
+                                    self.radix.fmt_byte(writer, slice[i], self.lowercase_hex)?;
+                                    write!(writer, "{}</span>", byte_suffix)?;
+                                }
+                            }
+                            separator = " ";
+                        }
+                    },
+                    Pane::Char => {
+                        write!(writer, "<td class=\"hexplay-char\">{}", escape(open))?;
+                        for (n, column) in format::char_columns(self, self.data, range.clone(), &padding).into_iter().enumerate() {
+                            write!(writer, "{}", escape(format::column_separator(self, format::char_column_bytes(self, n))))?;
+                            match column {
+                                Some((i, ch)) => write!(writer, "<span class=\"hexplay-char{}\" data-offset=\"{}\">{}</span>", classes(i), address + padding.left + i, escape(&ch.to_string()))?,
+                                None => write!(writer, " ")?,
+                            }
+                        }
+                    },
+                }
+                write!(writer, "{}</td>", escape(close))?;
+            }
+            writeln!(writer, "</tr>")?;
+        }
+        Ok(())
+    }
+}
+
+/// Escapes the characters that have a special meaning in HTML text and attribute values.
+/// Returns whether `id` can be used both as an HTML id and as a CSS selector without escaping.
+fn is_valid_id(id: &str) -> bool {
+    let mut chars = id.chars();
+    chars.next().is_some_and(|ch| ch.is_ascii_alphabetic())
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || ch == '-' || ch == '_')
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '&' => escaped.push_str("&amp;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn css_declarations(spec: &Spec) -> String {
+    let mut css = String::new();
+    if let Some(color) = spec.fg() {
+        css += &format!(" color: {};", css_color(color));
+    }
+    if let Some(color) = spec.bg() {
+        css += &format!(" background-color: {};", css_color(color));
+    }
+    if spec.bold() {
+        css += " font-weight: bold;";
+    }
+    if spec.italic() {
+        css += " font-style: italic;";
+    }
+    if spec.underline() {
+        css += " text-decoration: underline;";
+    }
+    css
+}
+
+fn css_color(color: &Color) -> String {
+    const ANSI: [&str; 16] = [
+        "#000000", "#800000", "#008000", "#808000", "#000080", "#800080", "#008080", "#c0c0c0",
+        "#808080", "#ff0000", "#00ff00", "#ffff00", "#0000ff", "#ff00ff", "#00ffff", "#ffffff",
+    ];
+
+    match *color {
+        Color::Black => "black".to_string(),
+        Color::Blue => "blue".to_string(),
+        Color::Green => "green".to_string(),
+        Color::Red => "red".to_string(),
+        Color::Cyan => "darkcyan".to_string(),
+        Color::Magenta => "magenta".to_string(),
+        Color::Yellow => "goldenrod".to_string(),
+        Color::White => "white".to_string(),
+        Color::Ansi256(n) if n < 16 => ANSI[n as usize].to_string(),
+        Color::Ansi256(n) if n < 232 => {
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            let n = n - 16;
+            format!("#{:02x}{:02x}{:02x}", level(n / 36), level(n / 6 % 6), level(n % 6))
+        },
+        Color::Ansi256(n) => {
+            let gray = 8 + (n - 232) * 10;
+            format!("#{:02x}{:02x}{:02x}", gray, gray, gray)
+        },
+        Color::Rgb(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        _ => "inherit".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use byte_mapping;
+    use color;
+    use format::{HexViewBuilder, Pane};
+
+    #[test]
+    fn every_byte_carries_its_offset_in_both_panes() {
+        let data = [0x41, 0x42];
+
+        let html = HexViewBuilder::new(&data)
+            .address_offset(0x11)
+            .row_width(4)
+            .finish()
+            .to_html()
+            .unwrap();
+
+        assert!(html.contains("<tr><td class=\"hexplay-address\">00000010</td><td class=\"hexplay-hex\">   \
+            <span class=\"hexplay-byte\" data-offset=\"17\">41</span> \
+            <span class=\"hexplay-byte\" data-offset=\"18\">42</span>   </td>\
+            <td class=\"hexplay-char\">|  \
+            <span class=\"hexplay-char\" data-offset=\"17\">A</span>\
+            <span class=\"hexplay-char\" data-offset=\"18\">B</span>  |</td></tr>"), "{}", html);
+    }
+
+    #[test]
+    fn color_ranges_become_css_classes() {
+        let data = [0x41, 0x42, 0x43];
+
+        let html = HexViewBuilder::new(&data)
+            .add_colors(vec![(color::red_bold(), 1..2)])
+            .finish()
+            .to_html()
+            .unwrap();
+
+        assert!(html.contains("#hexplay .hexplay-color-0 { color: red; font-weight: bold; }"));
+        assert!(html.contains("<span class=\"hexplay-byte hexplay-color-0\" data-offset=\"1\">42</span>"));
+        assert!(html.contains("<span class=\"hexplay-char hexplay-color-0\" data-offset=\"1\">B</span>"));
+        assert!(html.contains("<span class=\"hexplay-byte\" data-offset=\"2\">43</span>"));
+    }
+
+    #[test]
+    fn overlapping_color_ranges_give_a_byte_all_their_classes() {
+        let data = [0x41, 0x42, 0x43, 0x44];
+
+        let html = HexViewBuilder::new(&data)
+            .add_colors(vec![(color::blue(), 2..3), (color::red(), 1..4)])
+            .finish()
+            .to_html()
+            .unwrap();
+
+        assert!(html.contains("<span class=\"hexplay-byte\" data-offset=\"0\">41</span>"));
+        assert!(html.contains("<span class=\"hexplay-byte hexplay-color-1\" data-offset=\"1\">42</span>"));
+        assert!(html.contains("<span class=\"hexplay-byte hexplay-color-0 hexplay-color-1\" data-offset=\"2\">43</span>"));
+        assert!(html.contains("<span class=\"hexplay-char hexplay-color-1\" data-offset=\"3\">D</span>"));
+        // The rule of the narrower range comes last, so it takes precedence
+        let red = html.find(".hexplay-color-1 {").unwrap();
+        let blue = html.find(".hexplay-color-0 {").unwrap();
+        assert!(red < blue);
+    }
+
+    #[test]
+    fn an_id_that_is_not_a_plain_identifier_is_an_error() {
+        let view = HexViewBuilder::new(&[0x41]).finish();
+
+        for &id in &["", "1dump", "dump one", "a{}</style>", "d\u{e9}mo"] {
+            let result = view.fmt_html(&mut Vec::new(), id);
+            assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidInput, "{}", id);
+        }
+        assert!(view.fmt_html(&mut Vec::new(), "dump-1_a").is_ok());
+    }
+
+    #[test]
+    fn a_row_width_of_zero_is_an_error() {
+        let result = HexViewBuilder::new(&[0x41]).row_width(0).finish().to_html();
+
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn special_characters_are_escaped() {
+        let data = *b"<&\">";
+
+        let html = HexViewBuilder::new(&data)
+            .codepage(byte_mapping::CODEPAGE_0850)
+            .finish()
+            .to_html()
+            .unwrap();
+
+        assert!(html.contains(">&lt;</span>"));
+        assert!(html.contains(">&amp;</span>"));
+        assert!(html.contains(">&quot;</span>"));
+        assert!(html.contains(">&gt;</span>"));
+        assert!(!html.contains("><</span>"));
+    }
+
+    #[test]
+    fn the_cells_follow_the_panes() {
+        let data = [0x41];
+
+        let html = HexViewBuilder::new(&data)
+            .panes(&[Pane::Char, Pane::Hex])
+            .pane_delimiters(Pane::Char, "", "")
+            .row_width(1)
+            .finish()
+            .to_html()
+            .unwrap();
+
+        assert!(html.contains("<tr><td class=\"hexplay-char\"><span class=\"hexplay-char\" data-offset=\"0\">A</span></td>\
+            <td class=\"hexplay-hex\"><span class=\"hexplay-byte\" data-offset=\"0\">41</span></td></tr>"), "{}", html);
+    }
+
+    #[test]
+    fn repeated_rows_are_squeezed() {
+        let data = [0; 64];
+
+        let html = HexViewBuilder::new(&data)
+            .squeeze_repeats(true)
+            .finish()
+            .to_html()
+            .unwrap();
+
+        assert_eq!(html.matches("<tr>").count(), 3);
+        assert!(html.contains("<tr><td colspan=\"3\">*</td></tr>"));
+        assert!(html.contains("data-offset=\"63\""));
+    }
+}
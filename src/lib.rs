@@ -42,7 +42,9 @@
 //! You can add color to the hextable by specifying a [color::Spec](color/struct.Spec.html) and a range in the hextable to color,
 //! using HexViewBuilder's [add_colors](struct.HexViewBuilder.html#method.add_colors) method.
 //!
-//! **NB**: overlapping color ranges have unspecified behavior (not unsafe though, of course)
+//! Overlapping color ranges are layered: narrower ranges are drawn on top of wider ones, and of
+//! two ranges with the same length the one added last is on top. Each layer only overrides what
+//! its spec sets, so a bold range containing a red range renders the inner bytes in bold red.
 //!
 //! ```rust
 //! use hexplay::HexViewBuilder;
@@ -63,16 +65,56 @@
 //! // this will print to stdout
 //! view.print().unwrap();
 //! ```
+//!
+//! To explain what the colored ranges mean, use [annotate](struct.HexViewBuilder.html#method.annotate)
+//! instead. It colors the range as well, and lists its label in a legend below the hextable:
+//!
+//! ```rust
+//! use hexplay::HexViewBuilder;
+//!
+//! let data : Vec<u8> = (0u8..200u8).collect();
+//!
+//! let view = HexViewBuilder::new(&data[40..72])
+//!     .address_offset(40)
+//!     .annotate(0..2, "header.len", hexplay::color::red())
+//!     .annotate(2..4, "header.type", hexplay::color::green())
+//!     .annotation_margin(true)
+//!     .finish();
+//!
+//! view.print().unwrap();
+//! ```
 
 extern crate atty;
 extern crate termcolor;
 
 mod byte_mapping;
+mod diff;
 mod format;
+mod html;
+mod literal;
+mod rows;
+pub mod parse;
+pub mod records;
+mod sparse;
+mod stream;
 pub mod color;
 
+pub use byte_mapping::CODEPAGE_0037;
+pub use byte_mapping::CODEPAGE_0273;
+pub use byte_mapping::CODEPAGE_0500;
 pub use byte_mapping::CODEPAGE_0850;
+pub use byte_mapping::CODEPAGE_1047;
 pub use byte_mapping::CODEPAGE_1252;
 pub use byte_mapping::CODEPAGE_ASCII;
+pub use diff::HexDiffView;
+pub use diff::HexDiffViewBuilder;
+pub use format::CharEncoding;
+pub use format::Endian;
 pub use format::HexView;
 pub use format::HexViewBuilder;
+pub use format::Pane;
+pub use format::Radix;
+pub use literal::Literal;
+pub use rows::Row;
+pub use sparse::SparseHexView;
+pub use stream::HexStream;
@@ -0,0 +1,156 @@
+use std::io::{self, Write};
+
+use byte_mapping;
+use format::HexView;
+
+/// The language of an array literal, see [fmt_literal](struct.HexView.html#method.fmt_literal).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Literal {
+    /// An `unsigned char name[] = { ... };` array.
+    C,
+    /// A `const NAME: [u8; N] = [ ... ];` array.
+    Rust,
+    /// A `name = (b"..." ...)` bytes literal.
+    Python,
+}
+
+impl<'a> HexView<'a> {
+    /// Renders the data as an array literal, see [fmt_literal](#method.fmt_literal).
+    pub fn to_literal(&self, literal: Literal, name: &str, comments: bool) -> io::Result<String> {
+        let mut text = Vec::new();
+        self.fmt_literal(&mut text, literal, name, comments)?;
+        Ok(String::from_utf8(text).expect("the literal is written as utf-8"))
+    }
+
+    /// Writes the data to `writer` as an array literal named `name` in the given language.
+    ///
+    /// Every line holds `row_width` bytes. With `comments`, every line ends in a comment with the
+    /// address of its first byte and its bytes as characters of the codepage of the HexView. In C,
+    /// a `\` is shown as the replacement character, as it would continue the comment on the next line.
+    ///
+    /// A row width of 0, or empty data in C, which has no empty arrays, results in an `InvalidInput`
+    /// error.
+    pub fn fmt_literal<W: Write>(&self, writer: &mut W, literal: Literal, name: &str, comments: bool) -> io::Result<()> {
+        if self.row_width == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Invalid HexView::width"));
+        }
+        if literal == Literal::C && self.data.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "An empty C array is invalid"));
+        }
+        let lines: Vec<String> = self.data.chunks(self.row_width)
+            .map(|chunk| match literal {
+                Literal::C | Literal::Rust => chunk.iter().map(|byte| format!("0x{:02X},", byte)).collect::<Vec<_>>().join(" "),
+                Literal::Python => format!("b\"{}\"", chunk.iter().map(|byte| format!("\\x{:02X}", byte)).collect::<String>()),
+            })
+            .collect();
+        let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+        let comment = match literal {
+            Literal::C | Literal::Rust => "//",
+            Literal::Python => "#",
+        };
+
+        match literal {
+            Literal::C => writeln!(writer, "unsigned char {}[] = {{", name)?,
+            Literal::Rust => writeln!(writer, "const {}: [u8; {}] = [", name, self.data.len())?,
+            Literal::Python => writeln!(writer, "{} = (", name)?,
+        }
+        if literal == Literal::Python && self.data.is_empty() {
+            writeln!(writer, "    b\"\"")?;
+        }
+        for (row, (chunk, line)) in self.data.chunks(self.row_width).zip(&lines).enumerate() {
+            if comments {
+                let address = self.fmt_address(self.address_offset + row * self.row_width);
+                let text: String = chunk.iter()
+                    .map(|&byte| match byte_mapping::as_char(byte, self.codepage, self.replacement_character) {
+                        '\\' if literal == Literal::C => self.replacement_character,
+                        ch => ch,
+                    })
+                    .collect();
+                writeln!(writer, "    {:4$}  {} {} {}", line, comment, address, text, width)?;
+            } else {
+                writeln!(writer, "    {}", line)?;
+            }
+        }
+        match literal {
+            Literal::C => writeln!(writer, "}};"),
+            Literal::Rust => writeln!(writer, "];"),
+            Literal::Python => writeln!(writer, ")"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use format::HexViewBuilder;
+
+    #[test]
+    fn the_lines_of_a_c_array_follow_the_row_width() {
+        let data = [0x41, 0x42, 0x43, 0x00, 0xFF];
+
+        let literal = HexViewBuilder::new(&data)
+            .row_width(4)
+            .finish()
+            .to_literal(Literal::C, "fixture", false)
+            .unwrap();
+
+        assert_eq!(literal, "unsigned char fixture[] = {\n\
+                             \x20   0x41, 0x42, 0x43, 0x00,\n\
+                             \x20   0xFF,\n\
+                             };\n");
+    }
+
+    #[test]
+    fn a_rust_array_carries_its_length() {
+        let data = [0x41, 0x42, 0x43];
+
+        let literal = HexViewBuilder::new(&data)
+            .finish()
+            .to_literal(Literal::Rust, "FIXTURE", false)
+            .unwrap();
+
+        assert_eq!(literal, "const FIXTURE: [u8; 3] = [\n\
+                             \x20   0x41, 0x42, 0x43,\n\
+                             ];\n");
+    }
+
+    #[test]
+    fn comments_hold_the_address_and_the_characters() {
+        let data = [0x41, 0x42, 0x43, 0x00, 0x44];
+
+        let literal = HexViewBuilder::new(&data)
+            .address_offset(0x10)
+            .row_width(4)
+            .finish()
+            .to_literal(Literal::Python, "fixture", true)
+            .unwrap();
+
+        assert_eq!(literal, "fixture = (\n\
+                             \x20   b\"\\x41\\x42\\x43\\x00\"  # 00000010 ABC.\n\
+                             \x20   b\"\\x44\"              # 00000014 D\n\
+                             )\n");
+    }
+
+    #[test]
+    fn a_backslash_does_not_continue_a_c_comment() {
+        let literal = HexViewBuilder::new(b"AB\\CD")
+            .row_width(3)
+            .finish()
+            .to_literal(Literal::C, "fixture", true)
+            .unwrap();
+
+        assert_eq!(literal, "unsigned char fixture[] = {\n\
+                             \x20   0x41, 0x42, 0x5C,  // 00000000 AB.\n\
+                             \x20   0x43, 0x44,        // 00000003 CD\n\
+                             };\n");
+    }
+
+    #[test]
+    fn empty_data_gives_valid_literals() {
+        let view = HexViewBuilder::new(&[]).finish();
+
+        assert_eq!(view.to_literal(Literal::Rust, "EMPTY", true).unwrap(), "const EMPTY: [u8; 0] = [\n];\n");
+        assert_eq!(view.to_literal(Literal::Python, "empty", true).unwrap(), "empty = (\n    b\"\"\n)\n");
+        assert_eq!(view.to_literal(Literal::C, "empty", true).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,427 @@
+//! Provides a parser that turns the output of [HexView](../struct.HexView.html) back into bytes.
+//!
+//! The parser accepts dumps of any row width, with partial rows at the start or the end
+//! (as produced for an unaligned `address_offset` or an incomplete last row) and with
+//! squeezed (`*`) rows. The char pane of every line is checked against the hex pane using the
+//! configured codepage and replacement character.
+//!
+//! Only the default layout of the rows can be parsed: a hexadecimal address without prefix, the
+//! hex pane with two hexadecimal digits per byte (`Radix::Hexadecimal`, a group size of 1 and no
+//! column separators, prefixes or suffixes), and the char pane framed by `| ` and ` |` and decoded
+//! through the codepage. The header and the legend are not parsed either. Lines showing another
+//! address prefix, digit groups or another radix result in an `UnsupportedLayout` error.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use hexplay::HexViewBuilder;
+//! use hexplay::parse::Parser;
+//!
+//! let data: Vec<u8> = (0u8..200u8).collect();
+//! let dump = format!("{}", HexViewBuilder::new(&data[40..72]).address_offset(40).finish());
+//!
+//! let parsed = Parser::new().parse(&dump).unwrap();
+//!
+//! assert_eq!(parsed.address, 40);
+//! assert_eq!(parsed.data, &data[40..72]);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use byte_mapping;
+
+/// The bytes recovered from a dump, together with the address of the first byte.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Parsed {
+    pub address: usize,
+    pub data: Vec<u8>,
+}
+
+/// The reason a dump could not be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The line does not start with a hexadecimal address.
+    InvalidAddress,
+    /// The line does not have the `| ... |` frame of the char pane.
+    MissingCharPane,
+    /// The hex pane does not consist of space separated two digit columns.
+    InvalidHexPane,
+    /// The given column of the hex pane is not a hexadecimal byte.
+    InvalidHexByte { column: usize },
+    /// The line has a different number of columns than the lines before it.
+    InconsistentRowWidth { expected: usize, found: usize },
+    /// Blank columns were found somewhere else than at the start of the first line or at
+    /// the end of the last line.
+    UnexpectedPadding { column: usize },
+    /// The address of the line does not follow the address of the line before it.
+    UnexpectedAddress { expected: usize, found: usize },
+    /// The char pane does not contain as many characters as the hex pane has columns.
+    CharPaneWidth { expected: usize, found: usize },
+    /// The character in the char pane does not match the byte in the hex pane.
+    CharMismatch { column: usize, expected: char, found: char },
+    /// A `*` line is not surrounded by complete rows.
+    UnexpectedSqueeze,
+    /// The line is not in the default layout of a HexView, which is the only one that is parsed.
+    UnsupportedLayout,
+}
+
+/// The error returned by [Parser::parse](struct.Parser.html#method.parse).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line (starting at 1) on which the error was found.
+    pub line: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match self.kind {
+            ParseErrorKind::InvalidAddress => write!(f, "invalid address"),
+            ParseErrorKind::MissingCharPane => write!(f, "missing char pane"),
+            ParseErrorKind::InvalidHexPane => write!(f, "malformed hex pane"),
+            ParseErrorKind::InvalidHexByte { column } => write!(f, "invalid hex byte in column {}", column),
+            ParseErrorKind::InconsistentRowWidth { expected, found } => write!(f, "expected {} columns, found {}", expected, found),
+            ParseErrorKind::UnexpectedPadding { column } => write!(f, "unexpected padding in column {}", column),
+            ParseErrorKind::UnexpectedAddress { expected, found } => write!(f, "expected address {:X}, found {:X}", expected, found),
+            ParseErrorKind::CharPaneWidth { expected, found } => write!(f, "expected {} characters in the char pane, found {}", expected, found),
+            ParseErrorKind::CharMismatch { column, expected, found } => write!(f, "char pane shows '{}' in column {}, but the hex pane implies '{}'", found, column, expected),
+            ParseErrorKind::UnexpectedSqueeze => write!(f, "unexpected '*' line"),
+            ParseErrorKind::UnsupportedLayout => write!(f, "not in the default layout of a HexView"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses the output of [HexView](../struct.HexView.html) back into bytes.
+pub struct Parser<'a> {
+    codepage: &'a [char],
+    replacement_character: char,
+}
+
+impl<'a> Default for Parser<'a> {
+    fn default() -> Self {
+        Parser::new()
+    }
+}
+
+impl<'a> Parser<'a> {
+    /// Constructs a new Parser using codepage 850 and `.` as replacement character, which are
+    /// the defaults of the HexView.
+    pub fn new() -> Parser<'a> {
+        Parser {
+            codepage: byte_mapping::CODEPAGE_0850,
+            replacement_character: '.',
+        }
+    }
+
+    /// Configures the codepage that was used to produce the char pane.
+    pub fn codepage<'b: 'a>(mut self, codepage: &'b [char]) -> Parser<'a> {
+        self.codepage = codepage;
+        self
+    }
+
+    /// Configures the replacement character that was used to produce the char pane.
+    pub fn replacement_character(mut self, ch: char) -> Parser<'a> {
+        self.replacement_character = ch;
+        self
+    }
+
+    /// Parses the given dump.
+    ///
+    /// Empty lines and surrounding whitespace are ignored.
+    pub fn parse(&self, text: &str) -> Result<Parsed, ParseError> {
+        let lines: Vec<(usize, &str)> = text.lines()
+            .map(str::trim)
+            .enumerate()
+            .filter(|&(_, line)| !line.is_empty())
+            .map(|(i, line)| (i + 1, line))
+            .collect();
+
+        let mut parsed = Parsed { address: 0, data: Vec::new() };
+        let mut row_width = None;
+        let mut next_address = None;
+        let mut full_row: Option<Vec<u8>> = None;
+        let mut squeezed_row: Option<Vec<u8>> = None;
+
+        for (n, &(line_number, line)) in lines.iter().enumerate() {
+            let error = |kind| ParseError { line: line_number, kind };
+            let is_first = n == 0;
+            let is_last = n + 1 == lines.len();
+
+            if line == "*" {
+                if full_row.is_none() || squeezed_row.is_some() {
+                    return Err(error(ParseErrorKind::UnexpectedSqueeze));
+                }
+                squeezed_row = full_row.take();
+                continue;
+            }
+
+            let row = self.parse_line(line).map_err(&error)?;
+
+            match row_width {
+                None => row_width = Some(row.columns.len()),
+                Some(width) if width != row.columns.len() => {
+                    return Err(error(ParseErrorKind::InconsistentRowWidth { expected: width, found: row.columns.len() }));
+                },
+                _ => {},
+            }
+
+            if let Some(expected) = next_address {
+                if let Some(repeated) = squeezed_row.take() {
+                    if row.address < expected || (row.address - expected) % repeated.len() != 0 {
+                        return Err(error(ParseErrorKind::UnexpectedAddress { expected, found: row.address }));
+                    }
+                    for _ in 0..(row.address - expected) / repeated.len() {
+                        parsed.data.extend_from_slice(&repeated);
+                    }
+                } else if row.address != expected {
+                    return Err(error(ParseErrorKind::UnexpectedAddress { expected, found: row.address }));
+                }
+            }
+            next_address = Some(row.address + row.columns.len());
+
+            let left_padding = row.columns.iter().take_while(|column| column.is_none()).count();
+            let data_end = left_padding + row.columns[left_padding..].iter().take_while(|column| column.is_some()).count();
+            if left_padding != 0 && !is_first {
+                return Err(error(ParseErrorKind::UnexpectedPadding { column: 0 }));
+            }
+            if data_end != row.columns.len() && !is_last {
+                return Err(error(ParseErrorKind::UnexpectedPadding { column: data_end }));
+            }
+            if let Some(column) = row.columns[data_end..].iter().position(|column| column.is_some()) {
+                return Err(error(ParseErrorKind::UnexpectedPadding { column: data_end + column }));
+            }
+
+            if is_first {
+                parsed.address = row.address + left_padding;
+            }
+            let bytes: Vec<u8> = row.columns[left_padding..data_end].iter().map(|column| column.unwrap()).collect();
+            parsed.data.extend_from_slice(&bytes);
+            full_row = if bytes.len() == row.columns.len() && !bytes.is_empty() { Some(bytes) } else { None };
+        }
+
+        if squeezed_row.is_some() {
+            return Err(ParseError { line: lines.last().map_or(0, |&(line_number, _)| line_number), kind: ParseErrorKind::UnexpectedSqueeze });
+        }
+
+        Ok(parsed)
+    }
+
+    fn parse_line(&self, line: &str) -> Result<Row, ParseErrorKind> {
+        let address_end = line.find(|c: char| !c.is_ascii_hexdigit()).unwrap_or(line.len());
+        if address_end != 0 && line[address_end..].starts_with(|c: char| c.is_ascii_alphanumeric()) {
+            // An address prefix or an address in another radix
+            return Err(ParseErrorKind::UnsupportedLayout);
+        }
+        if address_end == 0 || !line[address_end..].starts_with("  ") {
+            return Err(ParseErrorKind::InvalidAddress);
+        }
+        let address = usize::from_str_radix(&line[..address_end], 16).map_err(|_| ParseErrorKind::InvalidAddress)?;
+
+        let rest = &line[address_end + 2..];
+        let hex_end = rest.find("  | ").ok_or(ParseErrorKind::MissingCharPane)?;
+        let hex_pane = &rest[..hex_end];
+        let char_pane = &rest[hex_end + 4..];
+        if !char_pane.ends_with(" |") {
+            return Err(ParseErrorKind::MissingCharPane);
+        }
+        let char_pane = &char_pane[..char_pane.len() - 2];
+
+        let columns = parse_hex_pane(hex_pane)?;
+
+        let chars: Vec<char> = char_pane.chars().collect();
+        if chars.len() != columns.len() {
+            return Err(ParseErrorKind::CharPaneWidth { expected: columns.len(), found: chars.len() });
+        }
+        for (column, (byte, &found)) in columns.iter().zip(chars.iter()).enumerate() {
+            let expected = match *byte {
+                Some(byte) => byte_mapping::as_char(byte, self.codepage, self.replacement_character),
+                None => ' ',
+            };
+            if expected != found {
+                return Err(ParseErrorKind::CharMismatch { column, expected, found });
+            }
+        }
+
+        Ok(Row { address, columns })
+    }
+}
+
+struct Row {
+    address: usize,
+    columns: Vec<Option<u8>>,
+}
+
+fn parse_hex_pane(hex_pane: &str) -> Result<Vec<Option<u8>>, ParseErrorKind> {
+    if hex_pane.is_empty() {
+        return Ok(Vec::new());
+    }
+    if !hex_pane.is_ascii() || (hex_pane.len() + 1) % 3 != 0 {
+        return Err(malformed_hex_pane(hex_pane));
+    }
+
+    let bytes = hex_pane.as_bytes();
+    let mut columns = Vec::with_capacity((hex_pane.len() + 1) / 3);
+    for column in 0..(hex_pane.len() + 1) / 3 {
+        let begin = column * 3;
+        if begin + 2 < bytes.len() && bytes[begin + 2] != b' ' {
+            return Err(malformed_hex_pane(hex_pane));
+        }
+        let digits = &hex_pane[begin..begin + 2];
+        if digits == "  " {
+            columns.push(None);
+        } else if digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            columns.push(Some(u8::from_str_radix(digits, 16).unwrap()));
+        } else {
+            return Err(ParseErrorKind::InvalidHexByte { column });
+        }
+    }
+    Ok(columns)
+}
+
+/// Tells a hex pane in another layout, like digit groups, another radix or a relative address
+/// column, apart from a garbled one.
+fn malformed_hex_pane(hex_pane: &str) -> ParseErrorKind {
+    let mut tokens = hex_pane.split_whitespace();
+    if tokens.clone().all(|token| token.chars().all(|c| c.is_ascii_alphanumeric())) && tokens.any(|token| token.len() != 2) {
+        ParseErrorKind::UnsupportedLayout
+    } else {
+        ParseErrorKind::InvalidHexPane
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use format::{HexViewBuilder, Radix};
+    use byte_mapping::{CODEPAGE_ASCII, CODEPAGE_1252};
+
+    fn round_trip(data: &[u8], address_offset: usize, row_width: usize) {
+        let dump = format!("{}", HexViewBuilder::new(data)
+            .address_offset(address_offset)
+            .row_width(row_width)
+            .finish());
+
+        let parsed = Parser::new().parse(&dump).unwrap();
+
+        assert_eq!(parsed.address, address_offset, "{}", dump);
+        assert_eq!(parsed.data, data, "{}", dump);
+    }
+
+    #[test]
+    fn formatted_data_can_be_parsed_back() {
+        let data: Vec<u8> = (0u16..300u16).map(|v| (v * 7) as u8).collect();
+
+        for &row_width in &[1, 3, 8, 16, 32] {
+            for &(begin, end) in &[(0, 300), (5, 300), (0, 37), (13, 14), (3, 3 + row_width), (17, 21)] {
+                round_trip(&data[begin..end], begin, row_width);
+            }
+        }
+    }
+
+    #[test]
+    fn squeezed_rows_are_expanded() {
+        let mut data = vec![0x41; 5];
+        data.extend_from_slice(&[0; 64]);
+        data.extend_from_slice(&[0x42; 3]);
+
+        let dump = format!("{}", HexViewBuilder::new(&data)
+            .address_offset(3)
+            .squeeze_repeats(true)
+            .finish());
+
+        let parsed = Parser::new().parse(&dump).unwrap();
+
+        assert!(dump.contains('*'));
+        assert_eq!(parsed.address, 3);
+        assert_eq!(parsed.data, data);
+    }
+
+    #[test]
+    fn a_dump_ending_in_repeated_rows_keeps_its_length() {
+        let data = [0xFF; 64];
+
+        let dump = format!("{}", HexViewBuilder::new(&data)
+            .squeeze_repeats(true)
+            .finish());
+
+        let parsed = Parser::new().parse(&dump).unwrap();
+
+        assert!(dump.contains('*'));
+        assert_eq!(parsed.data, &data[..]);
+    }
+
+    #[test]
+    fn the_codepage_can_be_configured() {
+        let data: Vec<u8> = (0u16..256u16).map(|v| v as u8).collect();
+
+        for codepage in &[CODEPAGE_ASCII, CODEPAGE_1252] {
+            let dump = format!("{}", HexViewBuilder::new(&data)
+                .codepage(codepage)
+                .replacement_character('?')
+                .finish());
+
+            let parsed = Parser::new()
+                .codepage(codepage)
+                .replacement_character('?')
+                .parse(&dump)
+                .unwrap();
+
+            assert_eq!(parsed.data, data);
+        }
+    }
+
+    #[test]
+    fn a_mismatching_char_pane_is_reported() {
+        let dump = "00000000  41 42 43  | ABD |";
+
+        let error = Parser::new().parse(dump).unwrap_err();
+
+        assert_eq!(error, ParseError { line: 1, kind: ParseErrorKind::CharMismatch { column: 2, expected: 'C', found: 'D' } });
+    }
+
+    #[test]
+    fn malformed_lines_are_reported() {
+        let parser = Parser::new();
+
+        assert_eq!(parser.parse("  41 42  | AB |").unwrap_err().kind, ParseErrorKind::InvalidAddress);
+        assert_eq!(parser.parse("00000000  41 42").unwrap_err().kind, ParseErrorKind::MissingCharPane);
+        assert_eq!(parser.parse("00000000  41 4G  | AB |").unwrap_err().kind, ParseErrorKind::InvalidHexByte { column: 1 });
+        assert_eq!(parser.parse("00000000  41  42  | AB |").unwrap_err().kind, ParseErrorKind::InvalidHexPane);
+        assert_eq!(parser.parse("00000000  41 42  | A |").unwrap_err().kind, ParseErrorKind::CharPaneWidth { expected: 2, found: 1 });
+    }
+
+    #[test]
+    fn other_layouts_are_reported_as_unsupported() {
+        let data = [0x41, 0x42, 0x43, 0x44];
+        let layouts = vec![
+            HexViewBuilder::new(&data).address_prefix("0x"),
+            HexViewBuilder::new(&data).group_size(2),
+            HexViewBuilder::new(&data).radix(Radix::Octal),
+            HexViewBuilder::new(&data).byte_prefix("0x"),
+            HexViewBuilder::new(&data).relative_address_column(true),
+        ];
+
+        for builder in layouts {
+            let dump = format!("{}", builder.finish());
+            assert_eq!(Parser::new().parse(&dump).unwrap_err().kind, ParseErrorKind::UnsupportedLayout, "{}", dump);
+        }
+    }
+
+    #[test]
+    fn inconsistent_rows_are_reported() {
+        let parser = Parser::new();
+
+        let error = parser.parse("00000000  41 42  | AB |\n00000004  43 44  | CD |").unwrap_err();
+        assert_eq!(error, ParseError { line: 2, kind: ParseErrorKind::UnexpectedAddress { expected: 2, found: 4 } });
+
+        let error = parser.parse("00000000  41 42  | AB |\n00000002  43     | C  |\n00000004  45 46  | EF |").unwrap_err();
+        assert_eq!(error, ParseError { line: 2, kind: ParseErrorKind::UnexpectedPadding { column: 1 } });
+
+        let error = parser.parse("00000000  41 42  | AB |\n00000002  43 44 45  | CDE |").unwrap_err();
+        assert_eq!(error, ParseError { line: 2, kind: ParseErrorKind::InconsistentRowWidth { expected: 2, found: 3 } });
+    }
+}
@@ -0,0 +1,414 @@
+//! Writes and reads the Intel HEX and Motorola S-record formats used to describe flash images.
+//!
+//! Both formats describe sparse memory, so [parse](fn.parse.html) returns the data as a list of
+//! [Region](struct.Region.html)s, each of which can be shown with a [HexView](../struct.HexView.html).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use hexplay::HexViewBuilder;
+//! use hexplay::records::{self, RecordFormat};
+//!
+//! let mut image = Vec::new();
+//! records::write(&mut image, RecordFormat::IntelHex, 0x0800_0000, b"firmware").unwrap();
+//!
+//! for region in records::parse(&String::from_utf8(image).unwrap()).unwrap() {
+//!     let view = HexViewBuilder::new(&region.data)
+//!         .address_offset(region.address)
+//!         .finish();
+//!     println!("{}", view);
+//! }
+//! ```
+
+use std;
+use std::cmp;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+
+/// The number of data bytes written per record.
+const RECORD_LENGTH: usize = 16;
+
+/// The record format written by [write](fn.write.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordFormat {
+    /// Intel HEX with extended linear address (type 04) records, for addresses up to 4 GiB.
+    IntelHex,
+    /// Intel HEX with extended segment address (type 02) records, for addresses up to 1 MiB.
+    IntelHexSegmented,
+    /// Motorola S-records with 16 bit addresses.
+    S19,
+    /// Motorola S-records with 24 bit addresses.
+    S28,
+    /// Motorola S-records with 32 bit addresses.
+    S37,
+}
+
+impl RecordFormat {
+    /// Returns the address just past the largest address that can be written in this format.
+    fn address_limit(self) -> u64 {
+        match self {
+            RecordFormat::IntelHex | RecordFormat::S37 => 1 << 32,
+            RecordFormat::IntelHexSegmented => 1 << 20,
+            RecordFormat::S19 => 1 << 16,
+            RecordFormat::S28 => 1 << 24,
+        }
+    }
+}
+
+/// A contiguous block of memory described by a record file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Region {
+    pub address: usize,
+    pub data: Vec<u8>,
+}
+
+/// The reason a record file could not be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RecordErrorKind {
+    /// The line starts with neither `:` nor `S`, or uses another format than the lines before it.
+    InvalidStart,
+    /// The line contains something else than pairs of hexadecimal digits.
+    InvalidHex,
+    /// The number of bytes on the line does not match its byte count.
+    InvalidLength { expected: usize, found: usize },
+    /// The checksum of the record does not match its contents.
+    ChecksumMismatch { expected: u8, found: u8 },
+    /// The record type is not supported.
+    UnsupportedRecordType { record_type: u8 },
+    /// A record follows the end of file record.
+    RecordAfterEnd,
+    /// The Intel HEX file does not end with an end of file (type 01) record.
+    MissingEnd,
+    /// The record describes bytes that an earlier record described already.
+    OverlappingData { address: usize },
+}
+
+/// The error returned by [parse](fn.parse.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordError {
+    /// The line (starting at 1) on which the error was found.
+    pub line: usize,
+    pub kind: RecordErrorKind,
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match self.kind {
+            RecordErrorKind::InvalidStart => write!(f, "not an Intel HEX or S-record line"),
+            RecordErrorKind::InvalidHex => write!(f, "invalid hexadecimal digits"),
+            RecordErrorKind::InvalidLength { expected, found } => write!(f, "expected {} bytes, found {}", expected, found),
+            RecordErrorKind::ChecksumMismatch { expected, found } => write!(f, "expected checksum {:02X}, found {:02X}", expected, found),
+            RecordErrorKind::UnsupportedRecordType { record_type } => write!(f, "unsupported record type {:02X}", record_type),
+            RecordErrorKind::RecordAfterEnd => write!(f, "record after the end of file record"),
+            RecordErrorKind::MissingEnd => write!(f, "missing end of file record"),
+            RecordErrorKind::OverlappingData { address } => write!(f, "data at {:X} is described twice", address),
+        }
+    }
+}
+
+impl Error for RecordError {}
+
+/// Writes `data`, starting at `address`, to `writer` in the given record format.
+///
+/// Returns an `InvalidInput` error if the data does not fit in the address space of the format.
+pub fn write<W: Write>(writer: &mut W, format: RecordFormat, address: usize, data: &[u8]) -> io::Result<()> {
+    if address as u64 + data.len() as u64 > format.address_limit() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "the data does not fit in the address space of the record format"));
+    }
+
+    match format {
+        RecordFormat::IntelHex | RecordFormat::IntelHexSegmented => write_intel_hex(writer, format, address, data),
+        RecordFormat::S19 | RecordFormat::S28 | RecordFormat::S37 => write_srec(writer, format, address, data),
+    }
+}
+
+fn write_intel_hex<W: Write>(writer: &mut W, format: RecordFormat, address: usize, data: &[u8]) -> io::Result<()> {
+    let mut bank = 0;
+    let mut offset = 0;
+    while offset < data.len() {
+        let current = address + offset;
+        if current >> 16 != bank {
+            bank = current >> 16;
+            match format {
+                RecordFormat::IntelHex => write_intel_record(writer, 0x04, 0, &[(bank >> 8) as u8, bank as u8])?,
+                _ => {
+                    let segment = bank << 12;
+                    write_intel_record(writer, 0x02, 0, &[(segment >> 8) as u8, segment as u8])?
+                },
+            }
+        }
+
+        // A record can not cross the end of a 64 KiB bank
+        let len = cmp::min(cmp::min(RECORD_LENGTH, data.len() - offset), 0x1_0000 - (current & 0xFFFF));
+        write_intel_record(writer, 0x00, current as u16, &data[offset..offset + len])?;
+        offset += len;
+    }
+    write_intel_record(writer, 0x01, 0, &[])
+}
+
+fn write_intel_record<W: Write>(writer: &mut W, record_type: u8, address: u16, data: &[u8]) -> io::Result<()> {
+    let mut record = vec![data.len() as u8, (address >> 8) as u8, address as u8, record_type];
+    record.extend_from_slice(data);
+    let checksum = record.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte)).wrapping_neg();
+    record.push(checksum);
+    writeln!(writer, ":{}", to_hex(&record))
+}
+
+fn write_srec<W: Write>(writer: &mut W, format: RecordFormat, address: usize, data: &[u8]) -> io::Result<()> {
+    let (data_type, end_type, address_size) = match format {
+        RecordFormat::S19 => (b'1', b'9', 2),
+        RecordFormat::S28 => (b'2', b'8', 3),
+        _ => (b'3', b'7', 4),
+    };
+
+    write_srec_record(writer, b'0', 0, 2, &[])?;
+    for (n, chunk) in data.chunks(RECORD_LENGTH).enumerate() {
+        write_srec_record(writer, data_type, address + n * RECORD_LENGTH, address_size, chunk)?;
+    }
+    write_srec_record(writer, end_type, 0, address_size, &[])
+}
+
+fn write_srec_record<W: Write>(writer: &mut W, record_type: u8, address: usize, address_size: usize, data: &[u8]) -> io::Result<()> {
+    let mut record = vec![(address_size + data.len() + 1) as u8];
+    record.extend((0..address_size).rev().map(|n| (address as u64 >> (8 * n)) as u8));
+    record.extend_from_slice(data);
+    let checksum = !record.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte));
+    record.push(checksum);
+    writeln!(writer, "S{}{}", record_type as char, to_hex(&record))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
+
+/// Parses an Intel HEX or Motorola S-record file into the regions of memory it describes.
+///
+/// The format is detected from the first line. The regions are sorted by address, and adjacent
+/// records are merged into a single region. Start address records (Intel HEX types 03 and 05,
+/// S-record types 7 to 9) and header and count records (S-record types 0, 5 and 6) carry no data
+/// and are skipped after their checksum has been validated.
+pub fn parse(text: &str) -> Result<Vec<Region>, RecordError> {
+    let mut blocks: Vec<(usize, Vec<u8>, usize)> = Vec::new();
+    let mut lines = text.lines().enumerate().map(|(n, line)| (n + 1, line.trim())).filter(|&(_, line)| !line.is_empty()).peekable();
+    let is_intel_hex = match lines.peek() {
+        Some(&(_, line)) => line.starts_with(':'),
+        None => return Ok(Vec::new()),
+    };
+
+    let mut base = 0;
+    let mut end_line = None;
+    let mut last_line = 0;
+    for (line, text) in lines {
+        let error = |kind| RecordError { line, kind };
+        last_line = line;
+        if end_line.is_some() {
+            return Err(error(RecordErrorKind::RecordAfterEnd));
+        }
+
+        if is_intel_hex {
+            let record = decode_record(text, ":", 5).map_err(error)?;
+            let address = (record[1] as usize) << 8 | record[2] as usize;
+            let data = &record[4..record.len() - 1];
+            match record[3] {
+                0x00 => blocks.push((base + address, data.to_vec(), line)),
+                0x01 => end_line = Some(line),
+                0x02 if data.len() == 2 => base = ((data[0] as usize) << 8 | data[1] as usize) << 4,
+                0x04 if data.len() == 2 => base = ((data[0] as usize) << 8 | data[1] as usize) << 16,
+                0x02 | 0x04 => return Err(error(RecordErrorKind::InvalidLength { expected: 7, found: record.len() })),
+                0x03 | 0x05 => {},
+                record_type => return Err(error(RecordErrorKind::UnsupportedRecordType { record_type })),
+            }
+        } else {
+            let record_type = match text.as_bytes().get(1) {
+                Some(&digit @ b'0'..=b'9') if text.starts_with('S') => digit - b'0',
+                _ => return Err(error(RecordErrorKind::InvalidStart)),
+            };
+            let address_size = match record_type {
+                0 | 1 | 5 | 9 => 2,
+                2 | 6 | 8 => 3,
+                3 | 7 => 4,
+                _ => return Err(error(RecordErrorKind::UnsupportedRecordType { record_type })),
+            };
+            let record = decode_record(text, &text[..2], address_size + 2).map_err(error)?;
+            let address = record[1..1 + address_size].iter().fold(0, |address, &byte| address << 8 | byte as usize);
+            match record_type {
+                1..=3 => blocks.push((address, record[1 + address_size..record.len() - 1].to_vec(), line)),
+                7..=9 => end_line = Some(line),
+                _ => {},
+            }
+        }
+    }
+
+    if is_intel_hex && end_line.is_none() {
+        return Err(RecordError { line: last_line, kind: RecordErrorKind::MissingEnd });
+    }
+    merge(blocks)
+}
+
+/// Decodes the hexadecimal digits following `start` and validates the byte count and the checksum
+/// of the record. Returns all bytes of the record, from the byte count up to the checksum.
+fn decode_record(text: &str, start: &str, min_length: usize) -> Result<Vec<u8>, RecordErrorKind> {
+    if !text.starts_with(start) {
+        return Err(RecordErrorKind::InvalidStart);
+    }
+    let digits = &text.as_bytes()[start.len()..];
+    if digits.len() % 2 != 0 {
+        return Err(RecordErrorKind::InvalidHex);
+    }
+    let record = digits.chunks(2)
+        .map(|pair| std::str::from_utf8(pair).ok().and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+        .collect::<Option<Vec<u8>>>()
+        .ok_or(RecordErrorKind::InvalidHex)?;
+
+    let is_intel_hex = start == ":";
+    let expected = match record.first() {
+        Some(&count) if is_intel_hex => count as usize + 5,
+        Some(&count) => count as usize + 1,
+        None => min_length,
+    };
+    if record.len() != expected || record.len() < min_length {
+        return Err(RecordErrorKind::InvalidLength { expected: cmp::max(expected, min_length), found: record.len() });
+    }
+
+    let (contents, found) = record.split_at(record.len() - 1);
+    let sum = contents.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte));
+    let expected = if is_intel_hex { sum.wrapping_neg() } else { !sum };
+    if expected != found[0] {
+        return Err(RecordErrorKind::ChecksumMismatch { expected, found: found[0] });
+    }
+    Ok(record)
+}
+
+/// Sorts the blocks of data by address and merges the adjacent ones into regions.
+fn merge(mut blocks: Vec<(usize, Vec<u8>, usize)>) -> Result<Vec<Region>, RecordError> {
+    blocks.sort_by_key(|&(address, _, _)| address);
+
+    let mut regions: Vec<Region> = Vec::new();
+    for (address, data, line) in blocks {
+        match regions.last_mut() {
+            Some(region) if address < region.address + region.data.len() => {
+                return Err(RecordError { line, kind: RecordErrorKind::OverlappingData { address } });
+            },
+            Some(region) if address == region.address + region.data.len() => {
+                region.data.extend_from_slice(&data);
+                continue;
+            },
+            _ => {},
+        }
+        if !data.is_empty() {
+            regions.push(Region { address, data });
+        }
+    }
+    Ok(regions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn written(format: RecordFormat, address: usize, data: &[u8]) -> String {
+        let mut text = Vec::new();
+        write(&mut text, format, address, data).unwrap();
+        String::from_utf8(text).unwrap()
+    }
+
+    #[test]
+    fn intel_hex_records_carry_a_checksum() {
+        let text = written(RecordFormat::IntelHex, 0x0100, &[0x21, 0x46, 0x01, 0x36]);
+
+        assert_eq!(text, ":04010000214601365D\n:00000001FF\n");
+    }
+
+    #[test]
+    fn intel_hex_switches_banks_with_extended_address_records() {
+        let data: Vec<u8> = (0..4).collect();
+
+        assert_eq!(written(RecordFormat::IntelHex, 0x1_FFFE, &data), ":020000040001F9\n\
+                                                                    :02FFFE00000100\n\
+                                                                    :020000040002F8\n\
+                                                                    :020000000203F9\n\
+                                                                    :00000001FF\n");
+        assert_eq!(written(RecordFormat::IntelHexSegmented, 0x1_FFFE, &data), ":020000021000EC\n\
+                                                                             :02FFFE00000100\n\
+                                                                             :020000022000DC\n\
+                                                                             :020000000203F9\n\
+                                                                             :00000001FF\n");
+    }
+
+    #[test]
+    fn s_records_use_the_address_size_of_their_format() {
+        let data = [0x41, 0x42];
+
+        assert_eq!(written(RecordFormat::S19, 0x1234, &data), "S0030000FC\nS1051234414231\nS9030000FC\n");
+        assert_eq!(written(RecordFormat::S28, 0x1234, &data), "S0030000FC\nS206001234414230\nS804000000FB\n");
+        assert_eq!(written(RecordFormat::S37, 0x1234, &data), "S0030000FC\nS3070000123441422F\nS70500000000FA\n");
+    }
+
+    #[test]
+    fn data_outside_the_address_space_is_rejected() {
+        let mut text = Vec::new();
+
+        let result = write(&mut text, RecordFormat::S19, 0xFFFF, &[0, 0]);
+
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn written_records_are_parsed_back_into_regions() {
+        let data: Vec<u8> = (0u32..100).map(|n| (n * 7) as u8).collect();
+
+        let cases = [
+            (RecordFormat::IntelHex, 0xFFC0),
+            (RecordFormat::IntelHexSegmented, 0xFFC0),
+            (RecordFormat::S19, 0xFF00),
+            (RecordFormat::S28, 0xFFC0),
+            (RecordFormat::S37, 0xFFC0),
+        ];
+
+        for &(format, address) in &cases {
+            let regions = parse(&written(format, address, &data)).unwrap();
+
+            assert_eq!(regions, vec![Region { address, data: data.clone() }], "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn sparse_records_are_parsed_into_sorted_regions() {
+        let text = "S1050010CCDD41\nS1050000AABB95\nS1050002EEFF0B\nS9030000FC\n";
+
+        let regions = parse(text).unwrap();
+
+        assert_eq!(regions, vec![
+            Region { address: 0x0000, data: vec![0xAA, 0xBB, 0xEE, 0xFF] },
+            Region { address: 0x0010, data: vec![0xCC, 0xDD] },
+        ]);
+    }
+
+    #[test]
+    fn checksum_errors_are_reported_with_their_line() {
+        let text = ":04010000214601365D\n:0401040021460136EF\n:00000001FF\n";
+
+        let error = parse(text).unwrap_err();
+
+        assert_eq!(error, RecordError { line: 2, kind: RecordErrorKind::ChecksumMismatch { expected: 0x59, found: 0xEF } });
+    }
+
+    #[test]
+    fn malformed_records_are_rejected() {
+        let cases = [
+            (":0401000021460136\n", RecordErrorKind::InvalidLength { expected: 9, found: 8 }),
+            (":04010000214601XXEE\n", RecordErrorKind::InvalidHex),
+            (":00000006FA\n", RecordErrorKind::UnsupportedRecordType { record_type: 6 }),
+            (":04010000214601365D\n", RecordErrorKind::MissingEnd),
+            (":00000001FF\n:00000001FF\n", RecordErrorKind::RecordAfterEnd),
+            (":04010000214601365D\nS9030000FC\n", RecordErrorKind::InvalidStart),
+            (":02010000214696\n:02010100214695\n:00000001FF\n", RecordErrorKind::OverlappingData { address: 0x0101 }),
+        ];
+
+        for &(text, ref kind) in &cases {
+            assert_eq!(parse(text).map_err(|error| error.kind), Err(kind.clone()), "{:?}", text);
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use std::ops::Range;
+
+use color::Spec;
+use format::{self, HexView};
+
+/// A row of a [HexView](struct.HexView.html), laid out like the formatted hextable lays it out.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Row<'a> {
+    /// The address of the first column of the row.
+    pub address: usize,
+    /// The number of blank columns before the first byte.
+    pub left_pad: usize,
+    /// The number of blank columns after the last byte.
+    pub right_pad: usize,
+    /// The bytes shown in the row.
+    pub bytes: &'a [u8],
+    /// The characters of the char pane: one for every byte, or for UTF-16 one for every code unit
+    /// that starts in the row.
+    pub chars: Vec<char>,
+    /// The colored ranges of `bytes`, with the composition of the color layers covering them.
+    pub spans: Vec<(Range<usize>, &'a Spec)>,
+}
+
+impl<'a> HexView<'a> {
+    /// Returns an iterator over the rows of the hextable, for building other frontends on top of
+    /// the layout of the HexView.
+    ///
+    /// Every row is returned, squeezing is left to the caller. A row width of 0 results in no rows.
+    pub fn rows(&self) -> impl Iterator<Item = Row<'_>> + '_ {
+        let begin_padding = if self.row_width == 0 { 0 } else { format::calculate_begin_padding(self.address_offset, self.row_width) };
+        let mut color_range = self.color_range();
+
+        (0..).map_while(move |row| {
+            if self.row_width == 0 {
+                return None;
+            }
+            let (range, padding) = format::row_at(self.data, begin_padding, self.row_width, row)?;
+            color_range.update_offset(range.start);
+
+            let mut spans: Vec<(Range<usize>, &Spec)> = Vec::new();
+            for i in 0..range.len() {
+                match (color_range.get(i), spans.last_mut()) {
+                    (Some(spec), Some(&mut (ref mut span, previous))) if span.end == i && previous == spec => span.end += 1,
+                    (Some(spec), _) => spans.push((i..i + 1, spec)),
+                    (None, _) => {},
+                }
+            }
+
+            Some(Row {
+                address: self.address_offset - begin_padding + row * self.row_width,
+                left_pad: padding.left,
+                right_pad: padding.right,
+                bytes: &self.data[range.clone()],
+                chars: format::row_chars(self, self.data, range).into_iter().flatten().collect(),
+                spans,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use color;
+    use format::{CharEncoding, Endian, HexViewBuilder};
+
+    #[test]
+    fn rows_carry_their_address_and_padding() {
+        let data: Vec<u8> = (0x41..0x47).collect();
+        let view = HexViewBuilder::new(&data)
+            .address_offset(0x13)
+            .row_width(4)
+            .finish();
+
+        let rows: Vec<Row> = view.rows().collect();
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!((rows[0].address, rows[0].left_pad, rows[0].right_pad, rows[0].bytes), (0x10, 3, 0, &data[0..1]));
+        assert_eq!((rows[1].address, rows[1].left_pad, rows[1].right_pad, rows[1].bytes), (0x14, 0, 0, &data[1..5]));
+        assert_eq!((rows[2].address, rows[2].left_pad, rows[2].right_pad, rows[2].bytes), (0x18, 0, 3, &data[5..6]));
+        assert_eq!(rows[1].chars, vec!['B', 'C', 'D', 'E']);
+    }
+
+    #[test]
+    fn spans_hold_the_composed_colors_of_the_row() {
+        let data = [0x41; 8];
+        let bold = color::Spec::new().set_bold(true).clone();
+        let view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .add_colors(vec![(bold.clone(), 1..7), (color::red(), 2..3)])
+            .finish();
+
+        let rows: Vec<Row> = view.rows().collect();
+
+        assert_eq!(rows[0].spans, vec![(1..2, &bold), (2..3, &color::red_bold()), (3..4, &bold)]);
+        assert_eq!(rows[1].spans, vec![(0..3, &bold)]);
+    }
+
+    #[test]
+    fn utf16_rows_have_a_char_per_code_unit() {
+        let data = [0x41, 0x00, 0x42, 0x00, 0x43];
+        let view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .char_encoding(CharEncoding::Utf16(Endian::Little))
+            .finish();
+
+        let rows: Vec<Row> = view.rows().collect();
+
+        assert_eq!(rows[0].chars, vec!['A', 'B']);
+        assert_eq!(rows[1].chars, vec!['.']);
+    }
+
+    #[test]
+    fn empty_data_has_a_single_empty_row() {
+        let view = HexViewBuilder::new(&[]).address_offset(2).row_width(4).finish();
+
+        let rows: Vec<Row> = view.rows().collect();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!((rows[0].address, rows[0].left_pad, rows[0].right_pad, rows[0].bytes.len()), (0, 2, 2, 0));
+    }
+}
@@ -0,0 +1,205 @@
+use std;
+use std::io;
+use atty;
+
+use termcolor::{BufferWriter, Buffer, ColorChoice, WriteColor};
+
+use color::ColorlessString;
+use format::{self, HexView};
+
+/// The SparseHexView struct displays disjoint regions of memory, such as the segments of an ELF
+/// file or the regions of an Intel HEX file, in address order.
+///
+/// The configuration is taken from a [HexView](struct.HexView.html), whose own data is ignored.
+/// The address of every region is absolute, and the color ranges and annotations of the view are
+/// relative to its `address_offset`, so with the default offset of 0 they are absolute addresses
+/// as well. The relative address column shows the offset within each region. The gaps between
+/// the regions are marked with a `-- gap of N bytes --` line, and the rows at the edges of a gap
+/// are padded.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexplay::{HexViewBuilder, SparseHexView};
+///
+/// let vectors = [0x00, 0x10, 0x00, 0x20];
+/// let code = b"\x4f\xf0\x00\x00";
+///
+/// let view = SparseHexView::new(vec![(0x0800_0100, &code[..]), (0x0800_0000, &vectors[..])], HexViewBuilder::new(&[]).finish());
+/// view.print().unwrap();
+/// ```
+pub struct SparseHexView<'a> {
+    view: HexView<'a>,
+    regions: Vec<(usize, &'a [u8])>,
+}
+
+impl<'a> SparseHexView<'a> {
+    /// Constructs a new SparseHexView showing the `(address, data)` regions as configured by
+    /// `view`. The regions are sorted by address, and empty regions are left out.
+    ///
+    /// Overlapping regions are shown one after the other, without a gap line between them. The
+    /// bytes of regions below the `address_offset` of the view are not colored.
+    pub fn new(mut regions: Vec<(usize, &'a [u8])>, mut view: HexView<'a>) -> SparseHexView<'a> {
+        regions.retain(|&(_, data)| !data.is_empty());
+        regions.sort_by_key(|&(address, _)| address);
+        if view.address_digits.is_none() {
+            let end = regions.iter().map(|&(address, data)| address + data.len()).max().unwrap_or(0);
+            view.address_digits = Some(view.address_radix.digits_of(end));
+        }
+
+        SparseHexView {
+            view,
+            regions,
+        }
+    }
+
+    /// Prints the regions to stdout. If any colors were given during construction, the specified ranges will be printed in color.
+    pub fn print(&self) -> io::Result<()> {
+        let cc = if self.view.force_color || atty::is(atty::Stream::Stdout) { ColorChoice::Auto } else { ColorChoice::Never };
+        let writer = BufferWriter::stdout(cc);
+        let mut buffer: Buffer = writer.buffer();
+        self.fmt(&mut buffer)?;
+        writer.print(&buffer)?;
+        Ok(())
+    }
+
+    pub fn fmt<W: WriteColor>(&self, buffer: &mut W) -> io::Result<()> {
+        format::fmt_header(buffer, &self.view)?;
+
+        let mut previous_end: Option<usize> = None;
+        for &(address, data) in &self.regions {
+            match previous_end {
+                Some(end) if end < address => write!(buffer, "\n-- gap of {} bytes --\n", address - end)?,
+                Some(_) => writeln!(buffer)?,
+                None => {},
+            }
+            let color_offset = address.wrapping_sub(self.view.address_offset);
+            self.view.fmt_rows_of(buffer, data, address, 0..data.len(), color_offset)?;
+            previous_end = Some(address + data.len());
+        }
+
+        format::fmt_legend(buffer, &self.view)
+    }
+}
+
+impl<'a> std::fmt::Display for SparseHexView<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.view.row_width == 0 {
+            write!(f, "Invalid HexView::width")?;
+            return Err(std::fmt::Error);
+        }
+        let mut string = ColorlessString(String::new());
+        match self.fmt(&mut string) {
+            Ok(()) => {
+                write!(f, "{}", string.0)
+            },
+            Err(e) => write!(f, "{}", e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use color;
+    use format::HexViewBuilder;
+
+    #[test]
+    fn regions_are_shown_in_address_order_with_their_gaps() {
+        let first = [0x41; 6];
+        let second = [0x42; 3];
+
+        let view = SparseHexView::new(vec![(0x1A, &second[..]), (0x02, &first[..])], HexViewBuilder::new(&[]).row_width(4).finish());
+
+        let result = format!("{}", view);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("00000000        41 41  |   AA |"));
+        assert_eq!(lines.next(), Some("00000004  41 41 41 41  | AAAA |"));
+        assert_eq!(lines.next(), Some("-- gap of 18 bytes --"));
+        assert_eq!(lines.next(), Some("00000018        42 42  |   BB |"));
+        assert_eq!(lines.next(), Some("0000001C  42           | B    |"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn rows_straddling_a_gap_are_padded_on_both_sides_of_it() {
+        let first = [0x41; 2];
+        let second = [0x42; 1];
+
+        let view = SparseHexView::new(vec![(0x10, &first[..]), (0x13, &second[..])], HexViewBuilder::new(&[]).row_width(4).finish());
+
+        let result = format!("{}", view);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("00000010  41 41        | AA   |"));
+        assert_eq!(lines.next(), Some("-- gap of 1 bytes --"));
+        assert_eq!(lines.next(), Some("00000010           42  |    B |"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn the_relative_address_is_the_offset_within_the_region() {
+        let first = [0x41; 6];
+        let second = [0x42; 4];
+
+        let view = SparseHexView::new(vec![(0x102, &first[..]), (0x200, &second[..])], HexViewBuilder::new(&[])
+            .row_width(4)
+            .relative_address_column(true)
+            .finish());
+
+        let result = format!("{}", view);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("00000100  00000000        41 41  |   AA |"));
+        assert_eq!(lines.next(), Some("00000104  00000002  41 41 41 41  | AAAA |"));
+        assert_eq!(lines.next(), Some("-- gap of 248 bytes --"));
+        assert_eq!(lines.next(), Some("00000200  00000000  42 42 42 42  | BBBB |"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn colors_and_annotations_use_absolute_addresses() {
+        let first = [0x41; 4];
+        let second = [0x42; 4];
+
+        let view = SparseHexView::new(vec![(0x100, &first[..]), (0x200, &second[..])], HexViewBuilder::new(&[])
+            .row_width(4)
+            .annotation_margin(true)
+            .annotate(0x201..0x203, "field", color::red())
+            .finish());
+
+        let result = format!("{}", view);
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("00000100  41 41 41 41  | AAAA |"));
+        assert_eq!(lines.next(), Some("-- gap of 252 bytes --"));
+        assert_eq!(lines.next(), Some("00000200  42 42 42 42  | BBBB |  field"));
+        assert_eq!(lines.next(), Some(""));
+        assert_eq!(lines.next(), Some("Legend:"));
+        assert_eq!(lines.next(), Some("  field  00000201..00000203"));
+        assert_eq!(lines.next(), None);
+    }
+
+    #[test]
+    fn regions_below_the_address_offset_are_not_colored() {
+        let below = [0x41; 4];
+        let above = [0x42; 4];
+
+        let view = SparseHexView::new(vec![(0x80, &below[..]), (0x100, &above[..])], HexViewBuilder::new(&[])
+            .address_offset(0x100)
+            .row_width(4)
+            .annotation_margin(true)
+            .annotate(0..2, "hdr", color::red())
+            .finish());
+
+        let mut buffer = Buffer::ansi();
+        view.fmt(&mut buffer).unwrap();
+        let result = String::from_utf8(buffer.into_inner()).unwrap();
+        let mut lines = result.lines();
+
+        assert_eq!(lines.next(), Some("00000080  41 41 41 41  | AAAA |"));
+        assert_eq!(lines.next(), Some("-- gap of 124 bytes --"));
+        assert!(lines.next().unwrap().contains("hdr"));
+    }
+}
@@ -0,0 +1,248 @@
+use std::cmp;
+use std::io::{self, Read};
+use atty;
+
+use termcolor::{BufferWriter, Buffer, ColorChoice, WriteColor};
+
+use format::{self, HexView, Padding, Squeeze, Squeezer};
+
+/// The HexStream struct formats the data of a reader row by row, without loading all of it into memory.
+///
+/// The configuration is taken from a [HexView](struct.HexView.html), whose own data is ignored.
+/// Color ranges are relative to the first byte read from the reader, just like they are relative
+/// to the start of the slice of a HexView.
+///
+/// # Examples
+///
+/// ```rust
+/// use hexplay::{HexStream, HexViewBuilder};
+///
+/// let file: &[u8] = b"Read me row by row";
+///
+/// let config = HexViewBuilder::new(&[])
+///     .address_offset(0x1000)
+///     .finish();
+///
+/// let mut stream = HexStream::new(file, config);
+/// stream.print().unwrap();
+/// ```
+pub struct HexStream<'a, R: Read> {
+    view: HexView<'a>,
+    reader: R,
+}
+
+impl<'a, R: Read> HexStream<'a, R> {
+    /// Constructs a new HexStream that reads from `reader` and formats the data as configured by `view`.
+    pub fn new(reader: R, mut view: HexView<'a>) -> HexStream<'a, R> {
+        if view.address_digits.is_none() {
+            view.address_digits = Some(view.address_radix.digits_of(usize::MAX));
+        }
+        HexStream {
+            view,
+            reader,
+        }
+    }
+
+    /// Reads the reader until its end and prints the hextable to stdout, one row at a time.
+    pub fn print(&mut self) -> io::Result<()> {
+        let cc = if self.view.force_color || atty::is(atty::Stream::Stdout) { ColorChoice::Auto } else { ColorChoice::Never };
+        let writer = BufferWriter::stdout(cc);
+        let mut buffer: Buffer = writer.buffer();
+        self.fmt_rows(&mut buffer, &mut |buffer: &mut Buffer| {
+            writer.print(buffer)?;
+            buffer.clear();
+            Ok(())
+        })
+    }
+
+    /// Reads the reader until its end and writes the hextable to `buffer`, one row at a time.
+    pub fn fmt<W: WriteColor>(&mut self, buffer: &mut W) -> io::Result<()> {
+        self.fmt_rows(buffer, &mut |_| Ok(()))
+    }
+
+    fn fmt_rows<W: WriteColor, F: FnMut(&mut W) -> io::Result<()>>(&mut self, buffer: &mut W, after_row: &mut F) -> io::Result<()> {
+        let row_width = self.view.row_width;
+        let mut left_padding = format::calculate_begin_padding(self.view.address_offset, row_width);
+        let mut address = self.view.address_offset - left_padding;
+        let mut offset = 0;
+        let mut color_range = self.view.color_range();
+        let mut separator = "";
+        // The bytes of the current row, preceded and followed by the bytes needed to decode the
+        // characters that cross its boundaries
+        let mut window: Vec<u8> = Vec::with_capacity(row_width + 2 * format::CHAR_CONTEXT);
+        let mut row_start = 0;
+        let mut is_at_end = false;
+        let mut squeezer = Squeezer::default();
+
+        format::fmt_header(buffer, &self.view)?;
+        loop {
+            let wanted = row_width - left_padding;
+            let filled = row_start + wanted + format::CHAR_CONTEXT;
+            if !is_at_end && window.len() < filled {
+                let len = window.len();
+                window.resize(filled, 0);
+                let read = read_row(&mut self.reader, &mut window[len..])?;
+                window.truncate(len + read);
+                is_at_end = len + read < filled;
+            }
+
+            let len = cmp::min(wanted, window.len() - row_start);
+            if len == 0 && offset != 0 {
+                break;
+            }
+
+            let row = row_start..row_start + len;
+            // Empty data is shown as a single row without bytes
+            let right_padding = if len == 0 { row_width - left_padding } else { format::calculate_end_padding(left_padding + len, row_width) };
+            let padding = Padding::new(left_padding, right_padding);
+            // The lookahead of the window is only missing at the end of the data
+            let is_last_row = row.end == window.len();
+            color_range.update_offset(offset);
+
+            match squeezer.squeeze(&self.view, &window[row.clone()], &padding, is_last_row, &color_range) {
+                Squeeze::Row => {
+                    write!(buffer, "{}", separator)?;
+                    format::fmt_line(buffer, &self.view, address, offset, &window, row.clone(), &mut color_range, &padding)?;
+                },
+                Squeeze::Start => write!(buffer, "{}*", separator)?,
+                Squeeze::Continue => {},
+            }
+            after_row(buffer)?;
+
+            if len < wanted {
+                break;
+            }
+            // Dropping an even number of bytes keeps the window aligned to UTF-16 code units
+            let kept = row.end.saturating_sub(format::CHAR_CONTEXT) / 2 * 2;
+            window.drain(..kept);
+            row_start = row.end - kept;
+            offset += len;
+            address += row_width;
+            left_padding = 0;
+            separator = "\n";
+        }
+
+        format::fmt_legend(buffer, &self.view)?;
+        after_row(buffer)
+    }
+}
+
+/// Fills `buf` from the reader, retrying on short reads, and returns the number of bytes read.
+/// Only returns less than `buf.len()` when the end of the reader has been reached.
+fn read_row<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut len = 0;
+    while len < buf.len() {
+        match reader.read(&mut buf[len..]) {
+            Ok(0) => break,
+            Ok(n) => len += n,
+            Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {},
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use color::{self, ColorlessString};
+    use format::{CharEncoding, Endian, HexViewBuilder};
+
+    /// A reader that never returns more than a few bytes at once.
+    struct TrickleReader<'a> {
+        data: &'a [u8],
+        chunk: usize,
+    }
+
+    impl<'a> Read for TrickleReader<'a> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = cmp::min(cmp::min(self.chunk, buf.len()), self.data.len());
+            buf[..len].copy_from_slice(&self.data[..len]);
+            self.data = &self.data[len..];
+            Ok(len)
+        }
+    }
+
+    fn stream_result(data: &[u8], builder: HexViewBuilder) -> String {
+        let mut string = ColorlessString(String::new());
+        let reader = TrickleReader { data, chunk: 3 };
+        HexStream::new(reader, builder.finish()).fmt(&mut string).unwrap();
+        string.0
+    }
+
+    #[test]
+    fn a_stream_is_formatted_like_a_view_of_the_same_data() {
+        let data: Vec<u8> = (0u16..300u16).map(|v| (v / 40) as u8).collect();
+
+        for &row_width in &[1, 7, 16] {
+            for &(address_offset, len) in &[(0, 0), (5, 0), (0, 300), (5, 300), (3, 13), (0, 32)] {
+                for &squeeze in &[false, true] {
+                    let data = &data[..len];
+                    let builder = |data| HexViewBuilder::new(data)
+                        .address_offset(address_offset)
+                        .row_width(row_width)
+                        .squeeze_repeats(squeeze)
+                        .column_header(len == 13)
+                        .add_colors(vec![(color::red(), 10..20)]);
+
+                    assert_eq!(stream_result(data, builder(&[])), format!("{}", builder(data).finish()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn utf8_characters_crossing_rows_are_decoded_like_in_a_view() {
+        let data = "Grüße, 世界! 😀 \u{80}".repeat(5).into_bytes();
+
+        for &row_width in &[1, 3, 7, 16] {
+            for &address_offset in &[0, 2] {
+                let builder = |data| HexViewBuilder::new(data)
+                    .address_offset(address_offset)
+                    .row_width(row_width)
+                    .char_encoding(CharEncoding::Utf8);
+
+                assert_eq!(stream_result(&data, builder(&[])), format!("{}", builder(&data).finish()));
+            }
+        }
+    }
+
+    #[test]
+    fn utf16_characters_crossing_rows_are_decoded_like_in_a_view() {
+        let data: Vec<u8> = "Grüße, 世界! 😀 \u{80}".repeat(5).encode_utf16().flat_map(|unit| unit.to_le_bytes().to_vec()).collect();
+
+        for &row_width in &[1, 3, 7, 16] {
+            for &address_offset in &[0, 1, 2] {
+                let builder = |data| HexViewBuilder::new(data)
+                    .address_offset(address_offset)
+                    .row_width(row_width)
+                    .char_encoding(CharEncoding::Utf16(Endian::Little));
+
+                assert_eq!(stream_result(&data, builder(&[])), format!("{}", builder(&data).finish()));
+            }
+        }
+    }
+
+    #[test]
+    fn automatic_address_digits_fit_any_address() {
+        let result = stream_result(&[0x41], HexViewBuilder::new(&[]).auto_address_digits().address_prefix("0x"));
+
+        assert_eq!(result, format!("0x{:01$X}  41{2:45}  | A{2:15} |", 0, format!("{:X}", usize::MAX).len(), ""));
+    }
+
+    #[test]
+    fn read_errors_are_returned() {
+        struct FailingReader;
+
+        impl Read for FailingReader {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::new(io::ErrorKind::Other, "broken"))
+            }
+        }
+
+        let mut string = ColorlessString(String::new());
+        let result = HexStream::new(FailingReader, HexViewBuilder::new(&[]).finish()).fmt(&mut string);
+
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::Other);
+    }
+}